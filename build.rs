@@ -0,0 +1,68 @@
+//! Generate the registry of solutions from the files in `src/days/`
+//!
+//! Each `src/days/yYYYY/dayNN.rs` file is declared as the module `days::yYYYY::dayNN`, and its `DayNN` type is added to
+//! the `SOLUTIONS` of the year, so that adding a solution only takes creating its file.
+use std::{env, fmt::Write as _, fs, path::Path};
+
+/// The numbers parsed from the names of the entries of `dir` which look like `{prefix}NN{suffix}`, in ascending order
+fn numbered_entries(dir: &Path, prefix: &str, suffix: &str) -> Vec<u32> {
+    let mut numbers: Vec<u32> = fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("reading {}: {e}", dir.display()))
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            name.strip_prefix(prefix)?
+                .strip_suffix(suffix)?
+                .parse()
+                .ok()
+        })
+        .collect();
+    numbers.sort_unstable();
+    numbers
+}
+
+fn main() {
+    let days_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src/days");
+    println!("cargo:rerun-if-changed={}", days_dir.display());
+    let mut registry = String::new();
+    let mut years = Vec::new();
+    for year in numbered_entries(&days_dir, "y", "") {
+        let year_dir = days_dir.join(format!("y{year}"));
+        if !year_dir.is_dir() {
+            continue;
+        }
+        let days = numbered_entries(&year_dir, "day", ".rs");
+        writeln!(registry, "pub mod y{year} {{").unwrap();
+        for day in &days {
+            let path = year_dir.join(format!("day{day:02}.rs"));
+            writeln!(registry, "    #[path = {:?}]", path.display().to_string()).unwrap();
+            writeln!(registry, "    pub mod day{day:02};").unwrap();
+        }
+        writeln!(
+            registry,
+            "\n    /// All the registered solutions for {year}, ordered by day"
+        )
+        .unwrap();
+        writeln!(
+            registry,
+            "    pub static SOLUTIONS: &[crate::days::Solution] = &["
+        )
+        .unwrap();
+        for day in &days {
+            writeln!(
+                registry,
+                "        crate::days::Solution::new::<day{day:02}::Day{day:02}>({year}),"
+            )
+            .unwrap();
+        }
+        writeln!(registry, "    ];\n}}\n").unwrap();
+        years.push(format!("y{year}::SOLUTIONS"));
+    }
+    writeln!(
+        registry,
+        "/// The registered solutions of each year, in chronological order\npub static YEARS: &[&[Solution]] = &[{}];",
+        years.join(", ")
+    )
+    .unwrap();
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("solutions.rs"), registry).unwrap();
+}
@@ -0,0 +1,203 @@
+//! Allocation profiling with a counting global allocator
+//!
+//! The binary installs [`CountingAllocator`] as its global allocator, which does nothing more than the system allocator
+//! until the profiling is [enabled](enable). The counters are global to the process, so the phases being profiled
+//! should not run concurrently with other work.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::Display,
+    hint::black_box,
+    sync::atomic::{AtomicBool, AtomicIsize, AtomicU64, Ordering},
+};
+
+use anyhow::{bail, Result};
+use serde::Serialize;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+/// Bytes currently allocated (negative if more memory was freed than allocated since the profiling was enabled)
+static LIVE: AtomicIsize = AtomicIsize::new(0);
+/// Highest value of [`LIVE`] since the start of the current phase
+static PEAK: AtomicIsize = AtomicIsize::new(0);
+
+/// A global allocator which wraps the system allocator and counts the allocations once the profiling is enabled
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    /// Record an allocation (or reallocation) of `size` bytes, of which `added` are new live bytes
+    fn record(size: usize, added: isize) {
+        if !ENABLED.load(Ordering::Relaxed) {
+            return;
+        }
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size as u64, Ordering::Relaxed);
+        let live = LIVE.fetch_add(added, Ordering::Relaxed) + added;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+    }
+
+    /// Record that `size` bytes were freed
+    fn release(size: usize) {
+        if ENABLED.load(Ordering::Relaxed) {
+            LIVE.fetch_sub(size as isize, Ordering::Relaxed);
+        }
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            Self::record(layout.size(), layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            Self::record(layout.size(), layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        Self::release(layout.size());
+    }
+
+    /// A reallocation counts as an allocation of the new size
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            Self::record(new_size, new_size as isize - layout.size() as isize);
+        }
+        new_ptr
+    }
+}
+
+/// Start counting the allocations
+///
+/// Fails if [`CountingAllocator`] is not the global allocator, since nothing would be counted.
+pub fn enable() -> Result<()> {
+    ENABLED.store(true, Ordering::Relaxed);
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    drop(black_box(Box::new(0u64)));
+    if ALLOCATIONS.load(Ordering::Relaxed) == before {
+        ENABLED.store(false, Ordering::Relaxed);
+        bail!("the counting allocator is not the global allocator");
+    }
+    Ok(())
+}
+
+/// Whether the allocations are being counted
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// The allocations made during a phase (e.g. parsing)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct AllocStats {
+    /// Number of allocations and reallocations
+    pub allocations: u64,
+    /// Total size of the allocations
+    pub bytes: u64,
+    /// Highest amount of memory allocated during the phase on top of what was allocated before it
+    pub peak_bytes: u64,
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, {} peak",
+            self.allocations,
+            human_bytes(self.bytes),
+            human_bytes(self.peak_bytes)
+        )
+    }
+}
+
+/// A size in bytes with a binary unit, e.g. `1.5 KiB`
+fn human_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+/// The state of the counters at the start of a phase
+#[derive(Debug, Clone, Copy)]
+pub struct Phase {
+    allocations: u64,
+    bytes: u64,
+    live: isize,
+}
+
+impl Phase {
+    /// Start a phase, if the profiling is enabled
+    pub fn start() -> Option<Self> {
+        if !is_enabled() {
+            return None;
+        }
+        let live = LIVE.load(Ordering::Relaxed);
+        PEAK.store(live, Ordering::Relaxed);
+        Some(Self {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            bytes: BYTES.load(Ordering::Relaxed),
+            live,
+        })
+    }
+
+    /// The allocations made since the start of the phase
+    pub fn stats(&self) -> AllocStats {
+        AllocStats {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed) - self.allocations,
+            bytes: BYTES.load(Ordering::Relaxed) - self.bytes,
+            peak_bytes: (PEAK.load(Ordering::Relaxed) - self.live).max(0) as u64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn test_phase() {
+        enable().unwrap();
+        let phase = Phase::start().unwrap();
+        let mut v: Vec<u8> = Vec::with_capacity(1 << 20);
+        v.push(1);
+        drop(black_box(v));
+        let stats = phase.stats();
+        // other tests may allocate at the same time
+        assert!(stats.allocations >= 1);
+        assert!(stats.bytes >= 1 << 20);
+        assert!(stats.peak_bytes >= 1 << 20);
+    }
+
+    #[test]
+    fn test_display() {
+        let stats = AllocStats {
+            allocations: 3,
+            bytes: 1536,
+            peak_bytes: 100,
+        };
+        assert_eq!(
+            stats.to_string(),
+            "3 allocations, 1.5 KiB allocated, 100 B peak"
+        );
+        assert_eq!(human_bytes(5 << 30), "5.0 GiB");
+    }
+}
@@ -0,0 +1,123 @@
+use std::{convert::Infallible, fmt::Display, str::FromStr};
+
+use serde::{Serialize, Serializer};
+
+/// The answer to a part of a puzzle, in a canonical form so that answers can be compared regardless of how they were
+/// computed or typed
+///
+/// Integers are compared by value (`"042"` and `42` are the same answer) and the items of a comma-separated list are
+/// stripped of the whitespace around them (`"4, 6,3 "` is `"4,6,3"`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    /// The part has no answer (e.g. there is no part 2 on the last day)
+    NotApplicable,
+}
+
+impl Answer {
+    /// How a part without an answer is displayed (and parsed back)
+    const NOT_APPLICABLE: &'static str = "n/a";
+
+    /// Whether there is an answer at all
+    pub fn is_applicable(&self) -> bool {
+        !matches!(self, Answer::NotApplicable)
+    }
+}
+
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s == Self::NOT_APPLICABLE {
+            return Ok(Answer::NotApplicable);
+        }
+        if let Ok(value) = s.parse() {
+            return Ok(Answer::Integer(value));
+        }
+        Ok(Answer::Text(
+            s.split(',').map(str::trim).collect::<Vec<_>>().join(","),
+        ))
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        let Ok(answer) = value.parse();
+        answer
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        value.as_str().into()
+    }
+}
+
+/// For parts which don't have an answer
+impl From<()> for Answer {
+    fn from(_: ()) -> Self {
+        Answer::NotApplicable
+    }
+}
+
+macro_rules! from_integers {
+    ($($int:ty),*) => {
+        $(impl From<$int> for Answer {
+            fn from(value: $int) -> Self {
+                Answer::Integer(value as i128)
+            }
+        })*
+    };
+}
+
+from_integers!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{value}"),
+            Answer::Text(text) => write!(f, "{text}"),
+            Answer::NotApplicable => write!(f, "{}", Self::NOT_APPLICABLE),
+        }
+    }
+}
+
+/// Answers are serialized as strings, like in the answers file, so that large integers don't lose precision
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalise() {
+        assert_eq!(Answer::from(" 042\n"), Answer::Integer(42));
+        assert_eq!(Answer::from(42usize), Answer::from("42"));
+        assert_eq!(Answer::from(-3i64), Answer::Integer(-3));
+        assert_eq!(Answer::from("4, 6 ,3 "), Answer::Text("4,6,3".to_string()));
+        assert_eq!(
+            Answer::from("cjt,dsd,fwm".to_string()),
+            Answer::Text("cjt,dsd,fwm".to_string())
+        );
+        assert_eq!(Answer::from(()), Answer::NotApplicable);
+        assert_eq!(Answer::from("n/a"), Answer::NotApplicable);
+        assert!(!Answer::NotApplicable.is_applicable());
+    }
+
+    #[test]
+    fn test_display() {
+        for answer in ["42", "-3", "4,6,3", "n/a", ""] {
+            assert_eq!(Answer::from(answer).to_string(), answer);
+        }
+        assert_eq!(
+            serde_json::to_string(&Answer::Integer(u64::MAX as i128 + 1)).unwrap(),
+            "\"18446744073709551616\""
+        );
+    }
+}
@@ -0,0 +1,163 @@
+use std::{collections::BTreeMap, fmt::Display, fs, path::Path};
+
+use anyhow::{Context as _, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{answer::Answer, days::Part};
+
+/// The location of the known answers file, relative to the working directory
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// The accepted answers for both parts of a day
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayAnswers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part2: Option<String>,
+}
+
+impl DayAnswers {
+    /// The accepted answer for `part`, if known
+    pub fn get(&self, part: Part) -> Option<Answer> {
+        match part {
+            Part::One => self.part1.as_deref().map(Answer::from),
+            Part::Two => self.part2.as_deref().map(Answer::from),
+        }
+    }
+}
+
+/// The store of accepted answers, keyed by year and then by day
+///
+/// ```toml
+/// [2024.1]
+/// part1 = "1234"
+/// part2 = "5678"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct KnownAnswers(BTreeMap<String, BTreeMap<String, DayAnswers>>);
+
+impl KnownAnswers {
+    /// Load the answers file at `path`, which is empty if the file doesn't exist yet
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e).context("reading the answers file"),
+        };
+        toml::from_str(&contents).context("parsing the answers file")
+    }
+
+    /// Save the answers to the file at `path`
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let contents = toml::to_string(self).context("serializing the answers")?;
+        fs::write(path, contents).context("writing the answers file")
+    }
+
+    /// The accepted answers for `day` of `year`, if any
+    pub fn get(&self, year: u32, day: u32) -> Option<&DayAnswers> {
+        self.0.get(&year.to_string())?.get(&day.to_string())
+    }
+
+    /// Record the accepted answer for `part` of `day` of `year`
+    pub fn set(&mut self, year: u32, day: u32, part: Part, answer: &Answer) {
+        let answers = self
+            .0
+            .entry(year.to_string())
+            .or_default()
+            .entry(day.to_string())
+            .or_default();
+        match part {
+            Part::One => answers.part1 = Some(answer.to_string()),
+            Part::Two => answers.part2 = Some(answer.to_string()),
+        }
+    }
+}
+
+/// The result of checking an answer against the accepted one
+#[derive(Debug)]
+pub enum Check {
+    /// The answer matches the accepted answer
+    Pass,
+    /// The answer differs from the accepted answer
+    Mismatch { answer: Answer, expected: Answer },
+    /// There is no accepted answer to compare with
+    Unrecorded(Answer),
+    /// The answer could not be computed
+    Error(anyhow::Error),
+}
+
+impl Check {
+    /// Compare a computed answer with the accepted one
+    pub fn new(answer: Result<Answer>, expected: Option<Answer>) -> Self {
+        match (answer, expected) {
+            (Err(e), _) => Check::Error(e),
+            (Ok(answer), None) => Check::Unrecorded(answer),
+            (Ok(answer), Some(expected)) if answer == expected => Check::Pass,
+            (Ok(answer), Some(expected)) => Check::Mismatch { answer, expected },
+        }
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Check::Pass => write!(f, "ok"),
+            Check::Mismatch { answer, expected } => {
+                write!(f, "FAIL (got {answer}, expected {expected})")
+            }
+            Check::Unrecorded(answer) => write!(f, "unrecorded ({answer})"),
+            Check::Error(e) => write!(f, "ERROR ({e})"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = r#"[2024.1]
+part1 = "11"
+part2 = "31"
+
+[2024.25]
+part1 = "3"
+"#;
+
+    #[test]
+    fn test_answers() {
+        let mut answers: KnownAnswers = toml::from_str(ANSWERS).unwrap();
+        let day1 = answers.get(2024, 1).unwrap();
+        assert_eq!(day1.get(Part::One), Some(Answer::Integer(11)));
+        assert_eq!(day1.get(Part::Two), Some(Answer::Integer(31)));
+        assert_eq!(answers.get(2024, 25).unwrap().get(Part::Two), None);
+        assert!(answers.get(2023, 1).is_none());
+        answers.set(2024, 25, Part::Two, &Answer::NotApplicable);
+        let reloaded: KnownAnswers = toml::from_str(&toml::to_string(&answers).unwrap()).unwrap();
+        assert_eq!(
+            reloaded.get(2024, 25).unwrap().get(Part::Two),
+            Some(Answer::NotApplicable)
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        assert!(matches!(
+            Check::new(Ok(11u64.into()), Some("011".into())),
+            Check::Pass
+        ));
+        assert!(matches!(
+            Check::new(Ok(12u64.into()), Some("11".into())),
+            Check::Mismatch { .. }
+        ));
+        assert!(matches!(
+            Check::new(Ok("12".into()), None),
+            Check::Unrecorded(_)
+        ));
+        assert!(matches!(
+            Check::new(Err(anyhow::anyhow!("no input")), Some("11".into())),
+            Check::Error(_)
+        ));
+    }
+}
@@ -0,0 +1,307 @@
+use std::{
+    collections::BTreeMap, fmt::Display, fs, hint::black_box, path::Path, time::Duration,
+    time::Instant,
+};
+
+use anyhow::{bail, Context as _, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    days::{read_input, Day, Part, Solution},
+    profile::Profile,
+};
+
+/// The default location of the benchmark baseline, relative to the working directory
+pub const BASELINE_FILE: &str = "bench_baseline.json";
+
+/// How many times each phase is run
+#[derive(Debug, Clone, Copy)]
+pub struct BenchOptions {
+    /// Number of runs which are discarded before measuring
+    pub warmup: usize,
+    /// Number of measured runs
+    pub iterations: usize,
+}
+
+/// Summary statistics of the measured run times of a phase, in nanoseconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stats {
+    pub iterations: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+    pub p95_ns: u64,
+}
+
+impl Stats {
+    /// Compute the statistics for a list of run times
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+        let n = samples.len().max(1);
+        let nanos = |d: &Duration| d.as_nanos() as u64;
+        // nearest-rank percentile
+        let p95 = (n * 95).div_ceil(100) - 1;
+        Self {
+            iterations: samples.len(),
+            min_ns: samples.first().map(nanos).unwrap_or_default(),
+            median_ns: samples.get(n / 2).map(nanos).unwrap_or_default(),
+            mean_ns: samples.iter().map(nanos).sum::<u64>() / n as u64,
+            p95_ns: samples.get(p95).map(nanos).unwrap_or_default(),
+        }
+    }
+
+    /// Relative change of the median compared to a previous measurement, if it exceeds `threshold`
+    ///
+    /// For instance, `Some(0.25)` means the median is 25% slower than before.
+    pub fn regression(&self, previous: &Stats, threshold: f64) -> Option<f64> {
+        if previous.median_ns == 0 {
+            return None;
+        }
+        let change = self.median_ns as f64 / previous.median_ns as f64 - 1.0;
+        (change > threshold).then_some(change)
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let fmt = |ns: u64| format!("{:.1?}", Duration::from_nanos(ns));
+        write!(
+            f,
+            "{:>10} {:>10} {:>10} {:>10}",
+            fmt(self.min_ns),
+            fmt(self.median_ns),
+            fmt(self.mean_ns),
+            fmt(self.p95_ns)
+        )
+    }
+}
+
+/// The statistics for each phase of a day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DayBench {
+    pub parser: Stats,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prepare: Option<Stats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_1: Option<Stats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_2: Option<Stats>,
+}
+
+impl DayBench {
+    /// The name and statistics of each benchmarked phase
+    pub fn phases(&self) -> impl Iterator<Item = (&'static str, &Stats)> {
+        [
+            ("parser", Some(&self.parser)),
+            ("prepare", self.prepare.as_ref()),
+            ("part_1", self.part_1.as_ref()),
+            ("part_2", self.part_2.as_ref()),
+        ]
+        .into_iter()
+        .filter_map(|(name, stats)| Some((name, stats?)))
+    }
+}
+
+/// Run `f` repeatedly and compute statistics over its run times
+fn measure<T>(options: &BenchOptions, mut f: impl FnMut() -> T) -> Stats {
+    for _ in 0..options.warmup {
+        black_box(f());
+    }
+    let samples = (0..options.iterations)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+    Stats::from_samples(samples)
+}
+
+/// Benchmark the parser, the preparation step and each implemented part of a [`Day`] separately
+pub fn bench_day<D: Day>(input_string: &str, options: &BenchOptions) -> Result<DayBench> {
+    // make sure the input is valid before measuring anything
+    let input = D::parse_input(input_string, &[])?;
+    let parser = measure(options, || D::parse_input(input_string, &[]));
+    let prepared = D::prepare(&input).context("preparing the input")?;
+    let prepare = D::HAS_PREPARE.then(|| measure(options, || D::prepare(&input)));
+    // and that the parts succeed, so that we don't measure how fast they fail
+    let part_1 = if D::PARTS.contains(Part::One) {
+        D::part_1(&input, &prepared).context("running part 1")?;
+        Some(measure(options, || D::part_1(&input, &prepared)))
+    } else {
+        None
+    };
+    let part_2 = if D::PARTS.contains(Part::Two) {
+        D::part_2(&input, &prepared).context("running part 2")?;
+        Some(measure(options, || D::part_2(&input, &prepared)))
+    } else {
+        None
+    };
+    Ok(DayBench {
+        parser,
+        prepare,
+        part_1,
+        part_2,
+    })
+}
+
+/// Benchmark results keyed by year and then by day, stored as JSON
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Baseline(BTreeMap<String, BTreeMap<String, DayBench>>);
+
+impl Baseline {
+    /// Load the baseline at `path`, which is empty if the file doesn't exist yet
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e).context("reading the baseline file"),
+        };
+        serde_json::from_str(&contents).context("parsing the baseline file")
+    }
+
+    /// Save the baseline to the file at `path`
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let contents = serde_json::to_string_pretty(self).context("serializing the baseline")?;
+        fs::write(path, contents).context("writing the baseline file")
+    }
+
+    /// The results for `day` of `year`, if any
+    pub fn get(&self, year: u32, day: u32) -> Option<&DayBench> {
+        self.0.get(&year.to_string())?.get(&day.to_string())
+    }
+
+    /// Record the results for `day` of `year`
+    pub fn set(&mut self, year: u32, day: u32, bench: DayBench) {
+        self.0
+            .entry(year.to_string())
+            .or_default()
+            .insert(day.to_string(), bench);
+    }
+}
+
+/// Benchmark each solution with the input of `profile`, print the statistics of each phase and compare them with the
+/// baseline at `baseline_path`
+///
+/// A phase is a regression when its median is more than `threshold` (e.g. `0.1` for 10%) slower than in the baseline.
+/// A failing day doesn't prevent the others from being benchmarked. With `save`, the results replace those of the
+/// baseline. Fails if any day failed or any phase regressed.
+pub fn bench_days<'a>(
+    profile: &Profile,
+    year: u32,
+    solutions: impl IntoIterator<Item = &'a Solution>,
+    options: &BenchOptions,
+    baseline_path: &str,
+    save: bool,
+    threshold: f64,
+) -> Result<()> {
+    let mut baseline = Baseline::load(baseline_path)?;
+    let mut regressions = 0;
+    let mut failures = 0;
+    print_header();
+    for solution in solutions {
+        let results = match read_input(profile.input_path(year, solution.day))
+            .and_then(|input_string| solution.bench(&input_string, options))
+        {
+            Ok(results) => results,
+            Err(e) => {
+                failures += 1;
+                println!("{:>3} ERROR: {e:#}", solution.day);
+                continue;
+            }
+        };
+        let previous = baseline.get(year, solution.day);
+        for (phase, stats) in results.phases() {
+            let regression = previous
+                .and_then(|prev| prev.phases().find(|(p, _)| *p == phase))
+                .and_then(|(_, prev)| stats.regression(prev, threshold));
+            match regression {
+                Some(change) => {
+                    regressions += 1;
+                    println!(
+                        "{:>3} {phase} {stats}  REGRESSION +{:.1}%",
+                        solution.day,
+                        change * 100.0
+                    );
+                }
+                None => println!("{:>3} {phase} {stats}", solution.day),
+            }
+        }
+        if save {
+            baseline.set(year, solution.day, results);
+        }
+    }
+    if save {
+        baseline.save(baseline_path)?;
+        println!("Saved the results to {baseline_path}");
+    }
+    match (failures, regressions) {
+        (0, 0) => Ok(()),
+        (0, _) => bail!("{regressions} phase(s) regressed compared to the baseline"),
+        (_, 0) => bail!("{failures} day(s) failed"),
+        _ => bail!(
+            "{failures} day(s) failed and {regressions} phase(s) regressed compared to the baseline"
+        ),
+    }
+}
+
+/// Benchmark a day against a generated input and print the statistics of each phase
+///
+/// The results are not compared with the baseline, which was measured on the real input.
+pub fn bench_generated(
+    solution: &Solution,
+    seed: u64,
+    size: usize,
+    options: &BenchOptions,
+) -> Result<()> {
+    let input_string = solution.generate(seed, size)?;
+    let results = solution
+        .bench(&input_string, options)
+        .with_context(|| format!("benchmarking day {}", solution.day))?;
+    print_header();
+    for (phase, stats) in results.phases() {
+        println!("{:>3} {phase} {stats}", solution.day);
+    }
+    Ok(())
+}
+
+/// The header of the statistics table
+fn print_header() {
+    println!(
+        "Day Phase  {:>10} {:>10} {:>10} {:>10}",
+        "min", "median", "mean", "p95"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples = (1..=100).rev().map(Duration::from_nanos).collect();
+        let stats = Stats::from_samples(samples);
+        assert_eq!(stats.iterations, 100);
+        assert_eq!(stats.min_ns, 1);
+        assert_eq!(stats.median_ns, 51);
+        assert_eq!(stats.mean_ns, 50);
+        assert_eq!(stats.p95_ns, 95);
+    }
+
+    #[test]
+    fn test_regression() {
+        let stats = |median_ns| Stats {
+            iterations: 1,
+            min_ns: median_ns,
+            median_ns,
+            mean_ns: median_ns,
+            p95_ns: median_ns,
+        };
+        assert!(stats(130)
+            .regression(&stats(100), 0.1)
+            .is_some_and(|change| (change - 0.3).abs() < 1e-9));
+        assert_eq!(stats(105).regression(&stats(100), 0.1), None);
+        assert_eq!(stats(50).regression(&stats(100), 0.1), None);
+    }
+}
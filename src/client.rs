@@ -0,0 +1,556 @@
+use std::{
+    cell::Cell,
+    fs,
+    path::Path,
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{bail, Context as _, Result};
+use chrono::{TimeDelta, Utc};
+
+use crate::{
+    answer::Answer,
+    days::{example_path, puzzle_path, Part},
+    event::{self, countdown, ensure_unlocked, is_unlocked, next_unlock},
+    html::{code_blocks, render_articles, user_name, Style},
+    profile::{Config, Profile, CONTACT_VAR},
+};
+
+/// The base URL of the Advent of Code website
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// The User-Agent sent with each request, so that the website's maintainers know who to contact
+pub fn user_agent(contact: &str) -> String {
+    format!(
+        "{}/{} ({contact})",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION")
+    )
+}
+
+/// The minimum time between two requests to the website
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(3);
+
+/// How many times the input is requested once the puzzle unlocks, and the delay before the first retry
+const WAIT_ATTEMPTS: u32 = 6;
+const WAIT_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// An HTTP client for the Advent of Code website, authenticated with the session cookie
+///
+/// Requests are throttled so that they are at least [`DEFAULT_INTERVAL`] apart, and the inputs and puzzles of the days
+/// which are not unlocked yet are never requested. No request is sent until a contact is set for the User-Agent.
+pub struct Client {
+    base_url: String,
+    session: String,
+    contact: Option<String>,
+    http: reqwest::blocking::Client,
+    interval: Duration,
+    last_request: Cell<Option<Instant>>,
+}
+
+impl Client {
+    /// Create a client with the session cookie of `profile` and the contact of the config
+    pub fn new(base_url: &str, profile: &Profile) -> Result<Self> {
+        let config = Config::load()?;
+        let session = profile.session(&config)?;
+        let client = Self::with_session(base_url, &session);
+        Ok(match config.contact() {
+            Some(contact) => client.with_contact(&contact),
+            None => client,
+        })
+    }
+
+    /// Create a client with the provided session cookie, and no contact yet
+    pub fn with_session(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            contact: None,
+            http: reqwest::blocking::Client::new(),
+            interval: DEFAULT_INTERVAL,
+            last_request: Cell::new(None),
+        }
+    }
+
+    /// Set the contact sent in the User-Agent, see [`user_agent`]
+    pub fn with_contact(self, contact: &str) -> Self {
+        Self {
+            contact: Some(contact.to_string()),
+            ..self
+        }
+    }
+
+    /// Change the minimum time between two requests
+    pub fn with_interval(self, interval: Duration) -> Self {
+        Self { interval, ..self }
+    }
+
+    /// Wait until the minimum time since the last request has elapsed
+    fn throttle(&self) {
+        if let Some(last) = self.last_request.get() {
+            thread::sleep(self.interval.saturating_sub(last.elapsed()));
+        }
+        self.last_request.set(Some(Instant::now()));
+    }
+
+    /// Send a request with the User-Agent and session cookie, once it is our turn
+    ///
+    /// Fails without sending anything if no contact is set.
+    fn send(
+        &self,
+        request: reqwest::blocking::RequestBuilder,
+    ) -> Result<reqwest::blocking::Response> {
+        let Some(contact) = &self.contact else {
+            bail!("no contact for the User-Agent, set {CONTACT_VAR} or the `contact` key of the config file (e.g. to your email address)");
+        };
+        self.throttle();
+        request
+            .header("user-agent", user_agent(contact))
+            .header("cookie", format!("session={};", self.session))
+            .send()
+            .context("sending HTTP request")
+    }
+
+    /// Send a GET request to `path` (relative to the base URL)
+    fn get(&self, path: &str) -> Result<reqwest::blocking::Response> {
+        self.send(self.http.get(format!("{}{path}", self.base_url)))
+    }
+
+    /// Download the input for `day` of `year`
+    pub fn input(&self, year: u32, day: u32) -> Result<String> {
+        ensure_unlocked(year, day, Utc::now())?;
+        let response = self
+            .get(&format!("/{year}/day/{day}/input"))?
+            .error_for_status()
+            .with_context(|| format!("retrieving the input for day {day}. Is the session cookie valid (see `whoami`)?"))?;
+        response.text().context("decoding response body as text")
+    }
+
+    /// Download the input for `day` of `year` to the file at `path`, unless it already exists and `force` is false
+    ///
+    /// The trailing newline is removed. Returns whether the file was (re)downloaded.
+    pub fn download_input(
+        &self,
+        year: u32,
+        day: u32,
+        path: impl AsRef<Path>,
+        force: bool,
+    ) -> Result<bool> {
+        let path = path.as_ref();
+        if !force && path.exists() {
+            return Ok(false);
+        }
+        let text = self.input(year, day)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("creating the inputs directory")?;
+        }
+        fs::write(path, text.trim_end_matches('\n')).context("writing input to file")?;
+        Ok(true)
+    }
+
+    /// Download the HTML page of the puzzle for `day` of `year`
+    pub fn puzzle(&self, year: u32, day: u32) -> Result<String> {
+        ensure_unlocked(year, day, Utc::now())?;
+        let response = self
+            .get(&format!("/{year}/day/{day}"))?
+            .error_for_status()
+            .with_context(|| format!("retrieving the puzzle for day {day}"))?;
+        response.text().context("decoding response body as text")
+    }
+
+    /// Download the calendar page of `year`, which shows the stars of the account
+    pub fn calendar(&self, year: u32) -> Result<String> {
+        let response = self
+            .get(&format!("/{year}"))?
+            .error_for_status()
+            .with_context(|| format!("retrieving the calendar of {year}"))?;
+        response.text().context("decoding response body as text")
+    }
+
+    /// Download the JSON of the private leaderboard `id` for `year`, which must be readable by the account
+    ///
+    /// Mind the website's limit of one request every 15 minutes, see [`crate::leaderboard`].
+    pub fn leaderboard(&self, year: u32, id: u64) -> Result<String> {
+        let response = self
+            .get(&format!("/{year}/leaderboard/private/view/{id}.json"))?
+            .error_for_status()
+            .with_context(|| format!("retrieving the private leaderboard {id}"))?;
+        response.text().context("decoding response body as text")
+    }
+
+    /// The name of the account which the session cookie belongs to, as shown on the page at `endpoint`
+    ///
+    /// Fails if the website doesn't consider us logged in, e.g. because the cookie expired.
+    pub fn whoami(&self, endpoint: &str) -> Result<String> {
+        let response = self
+            .get(endpoint)?
+            .error_for_status()
+            .context("retrieving the account page")?;
+        let html = response.text().context("decoding response body as text")?;
+        user_name(&html).with_context(|| {
+            format!(
+                "not logged in at {}{endpoint}, the session cookie is invalid or expired",
+                self.base_url
+            )
+        })
+    }
+
+    /// Post an answer for `part` of `day` of `year`, returning the HTML page sent back by the server
+    pub fn submit(&self, year: u32, day: u32, part: Part, answer: &Answer) -> Result<String> {
+        if !answer.is_applicable() {
+            bail!(
+                "part {} of day {day} has no answer to submit",
+                part.number()
+            );
+        }
+        let url = format!("{}/{year}/day/{day}/answer", self.base_url);
+        let level = part.number().to_string();
+        let answer = answer.to_string();
+        let response = self
+            .send(
+                self.http
+                    .post(url)
+                    .form(&[("level", level.as_str()), ("answer", answer.as_str())]),
+            )
+            .context("submitting the answer")?
+            .error_for_status()
+            .with_context(|| format!("submitting the answer for day {day}. Is the session cookie valid (see `whoami`)?"))?;
+        response.text().context("decoding response body as text")
+    }
+}
+
+/// Download the input of `profile` for `day` of `year`, unless it was already downloaded and `force` is false
+pub fn get_input(
+    client: &Client,
+    profile: &Profile,
+    year: u32,
+    day: u32,
+    force: bool,
+) -> Result<()> {
+    let path = profile.input_path(year, day);
+    if client.download_input(year, day, &path, force)? {
+        println!("Successfully downloaded input to {path}");
+    } else {
+        println!("{path} already exists, skipping (use --force to download it again)");
+    }
+    Ok(())
+}
+
+/// Save each example block of the puzzle for `day` of `year` to its example file, unless they were already saved and
+/// `force` is false
+///
+/// The examples are the same for all profiles.
+pub fn get_examples(client: &Client, year: u32, day: u32, force: bool) -> Result<()> {
+    let first = example_path(year, day, 1);
+    if !force && fs::exists(&first).unwrap_or_default() {
+        println!("{first} already exists, skipping (use --force to download the examples again)");
+        return Ok(());
+    }
+    let html = client.puzzle(year, day)?;
+    let blocks = code_blocks(&html);
+    if blocks.is_empty() {
+        bail!("no example found in the puzzle for day {day}");
+    }
+    fs::create_dir_all(format!("inputs/{year}/examples"))
+        .context("creating the examples directory")?;
+    for (k, block) in blocks.iter().enumerate() {
+        let path = example_path(year, day, k + 1);
+        fs::write(&path, block.trim_end_matches('\n')).context("writing example to file")?;
+        println!("Saved example {} to {path}", k + 1);
+    }
+    Ok(())
+}
+
+/// Download the examples of `day` of `year` with `examples`, or else the input of `profile` (see [`get_examples`] and
+/// [`get_input`])
+pub fn get_day(
+    client: &Client,
+    profile: &Profile,
+    year: u32,
+    day: u32,
+    examples: bool,
+    force: bool,
+) -> Result<()> {
+    if examples {
+        get_examples(client, year, day, force)
+    } else {
+        get_input(client, profile, year, day, force)
+    }
+}
+
+/// Download the inputs (or the examples) of all the days of the event of `year` which are unlocked, see [`get_day`]
+///
+/// The inputs come before the solutions, so the days don't need to be implemented.
+pub fn get_all(
+    client: &Client,
+    profile: &Profile,
+    year: u32,
+    examples: bool,
+    force: bool,
+) -> Result<()> {
+    for day in event::days(year) {
+        if !is_unlocked(year, day, Utc::now()) {
+            println!("Day {day} is not unlocked yet, stopping");
+            break;
+        }
+        get_day(client, profile, year, day, examples, force)?;
+    }
+    Ok(())
+}
+
+/// Count down to the unlock of the next puzzle, then download its input (retrying while the website is busy)
+///
+/// Returns the year and day of the puzzle.
+pub fn wait_for_input(client: &Client, profile: &Profile, force: bool) -> Result<(u32, u32)> {
+    let (year, day, unlock) = next_unlock(Utc::now());
+    eprintln!(
+        "Waiting for day {day} of {year}, which unlocks at {}",
+        unlock.format("%Y-%m-%d %H:%M UTC")
+    );
+    loop {
+        let remaining = unlock - Utc::now();
+        if remaining <= TimeDelta::zero() {
+            break;
+        }
+        eprint!("\r\x1b[KUnlocking in {}", countdown(remaining));
+        let remaining = remaining.to_std().unwrap_or_default();
+        thread::sleep(remaining.min(Duration::from_secs(1)));
+    }
+    eprintln!("\r\x1b[KDay {day} is unlocked");
+    retry(WAIT_ATTEMPTS, WAIT_RETRY_DELAY, || {
+        get_input(client, profile, year, day, force)
+    })?;
+    Ok((year, day))
+}
+
+/// The text of the parts of a puzzle which are unlocked, rendered in `style`
+///
+/// The page is cached, and only downloaded again when it doesn't have the second part yet (since it appears once the
+/// first one is solved) or with `refresh`. Without a connection, the cached page is used anyway.
+pub fn read_puzzle(
+    base_url: &str,
+    profile: &Profile,
+    year: u32,
+    day: u32,
+    refresh: bool,
+    style: Style,
+) -> Result<String> {
+    let path = puzzle_path(year, day);
+    let cached = fs::read_to_string(&path).ok();
+    let complete = cached
+        .as_deref()
+        .is_some_and(|html| render_articles(html, Style::Plain).len() >= 2);
+    let html = match cached {
+        Some(html) if complete && !refresh => html,
+        cached => {
+            match Client::new(base_url, profile).and_then(|client| client.puzzle(year, day)) {
+                Ok(html) => {
+                    fs::create_dir_all(format!("inputs/puzzles/{year}"))
+                        .context("creating the puzzles directory")?;
+                    fs::write(&path, &html).context("writing puzzle to file")?;
+                    html
+                }
+                Err(e) => match cached {
+                    Some(html) if !refresh => {
+                        eprintln!("Could not download the puzzle again ({e:#}), using {path}");
+                        html
+                    }
+                    _ => return Err(e),
+                },
+            }
+        }
+    };
+    let articles = render_articles(&html, style);
+    if articles.is_empty() {
+        bail!("no puzzle text found in {path}");
+    }
+    Ok(articles.join("\n\n"))
+}
+
+/// Call `f` until it succeeds, at most `attempts` times, waiting `delay` after the first failure and twice as long
+/// after each following one
+///
+/// This is meant for the first requests after a puzzle unlocks, which may fail while the website is under load.
+pub fn retry<T>(attempts: u32, delay: Duration, mut f: impl FnMut() -> Result<T>) -> Result<T> {
+    let (mut attempt, mut delay) = (1, delay);
+    loop {
+        match f() {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt >= attempts => {
+                return Err(e.context(format!("giving up after {attempt} attempts")))
+            }
+            Err(e) => {
+                eprintln!("Attempt {attempt} failed ({e:#}), retrying in {delay:?}");
+                thread::sleep(delay);
+                attempt += 1;
+                delay *= 2;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read as _, Write as _},
+        net::TcpListener,
+        thread,
+    };
+
+    use super::*;
+
+    const CONTACT: &str = "alice@example.com";
+
+    /// Serve a single HTTP request with `body`, returning the raw request that was received
+    fn stub_server(body: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = String::new();
+            let mut buf = [0; 4096];
+            // read the headers, then the body according to its announced length
+            while !request.contains("\r\n\r\n") {
+                let n = stream.read(&mut buf).unwrap();
+                request.push_str(&String::from_utf8_lossy(&buf[..n]));
+            }
+            let headers_len = request.find("\r\n\r\n").unwrap() + 4;
+            let body_len: usize = request[..headers_len]
+                .lines()
+                .find_map(|l| {
+                    l.to_lowercase()
+                        .strip_prefix("content-length: ")?
+                        .parse()
+                        .ok()
+                })
+                .unwrap_or_default();
+            while request.len() < headers_len + body_len {
+                let n = stream.read(&mut buf).unwrap();
+                request.push_str(&String::from_utf8_lossy(&buf[..n]));
+            }
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            request
+        });
+        (base_url, handle)
+    }
+
+    #[test]
+    fn test_submit() {
+        let (base_url, handle) = stub_server("<article><p>That's the right answer!</p></article>");
+        let client = Client::with_session(&base_url, "abc").with_contact(CONTACT);
+        assert!(client
+            .submit(2024, 25, Part::Two, &Answer::NotApplicable)
+            .is_err());
+        let html = client
+            .submit(2024, 17, Part::Two, &"1, 2,3".into())
+            .unwrap();
+        assert!(html.contains("That's the right answer!"));
+        let request = handle.join().unwrap();
+        assert!(request.starts_with("POST /2024/day/17/answer "));
+        assert!(request.contains("session=abc;"));
+        assert!(request.ends_with("level=2&answer=1%2C2%2C3"));
+    }
+
+    #[test]
+    fn test_download_input() {
+        let (base_url, handle) = stub_server("1 2\n3 4\n");
+        let client = Client::with_session(&base_url, "abc")
+            .with_contact(CONTACT)
+            .with_interval(Duration::ZERO);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("day01.txt");
+        assert!(client.download_input(2024, 1, &path, false).unwrap());
+        let request = handle.join().unwrap();
+        assert!(request.starts_with("GET /2024/day/1/input "));
+        assert!(request.contains(&format!("user-agent: {}", user_agent(CONTACT))));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1 2\n3 4");
+        // the stub server is gone, so this would fail if the input was requested again
+        assert!(!client.download_input(2024, 1, &path, false).unwrap());
+        assert!(client.download_input(2024, 1, &path, true).is_err());
+        // future puzzles are never requested
+        assert!(client
+            .input(2999, 1)
+            .unwrap_err()
+            .to_string()
+            .contains("not unlocked yet"));
+    }
+
+    #[test]
+    fn test_leaderboard() {
+        let (base_url, handle) = stub_server(include_str!("../tests/fixtures/leaderboard.json"));
+        let client = Client::with_session(&base_url, "abc").with_contact(CONTACT);
+        let json = client.leaderboard(2024, 1).unwrap();
+        assert!(handle
+            .join()
+            .unwrap()
+            .starts_with("GET /2024/leaderboard/private/view/1.json "));
+        assert!(crate::leaderboard::Leaderboard::parse(&json).is_ok());
+    }
+
+    #[test]
+    fn test_whoami() {
+        let (base_url, handle) = stub_server(
+            r#"<header><div class="user">alice <span class="star-count">4*</span></div></header>"#,
+        );
+        let client = Client::with_session(&base_url, "abc").with_contact(CONTACT);
+        assert_eq!(client.whoami("/settings").unwrap(), "alice");
+        let request = handle.join().unwrap();
+        assert!(request.starts_with("GET /settings "));
+        assert!(request.contains("session=abc;"));
+        let (base_url, _) = stub_server("<header><a href=\"/auth/login\">[Log In]</a></header>");
+        let client = Client::with_session(&base_url, "expired").with_contact(CONTACT);
+        assert!(client
+            .whoami("/settings")
+            .unwrap_err()
+            .to_string()
+            .contains("the session cookie is invalid or expired"));
+    }
+
+    #[test]
+    fn test_no_contact() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        listener.set_nonblocking(true).unwrap();
+        let client = Client::with_session(&base_url, "abc");
+        assert!(client
+            .calendar(2024)
+            .unwrap_err()
+            .to_string()
+            .contains("no contact for the User-Agent"));
+        assert!(client.submit(2024, 1, Part::One, &"42".into()).is_err());
+        // nothing was sent
+        assert!(listener.accept().is_err());
+    }
+
+    #[test]
+    fn test_throttle() {
+        let client = Client::with_session("http://localhost", "abc")
+            .with_interval(Duration::from_millis(50));
+        let start = Instant::now();
+        client.throttle();
+        assert!(start.elapsed() < Duration::from_millis(50));
+        client.throttle();
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_retry() {
+        let mut calls = 0;
+        let value = retry(3, Duration::ZERO, || {
+            calls += 1;
+            if calls < 3 {
+                bail!("not yet");
+            }
+            Ok(calls)
+        });
+        assert_eq!(value.unwrap(), 3);
+        let error = retry(2, Duration::ZERO, || -> Result<()> { bail!("not yet") }).unwrap_err();
+        assert_eq!(format!("{error:#}"), "giving up after 2 attempts: not yet");
+    }
+}
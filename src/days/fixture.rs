@@ -0,0 +1,44 @@
+//! A [`Day`] for the tests of the modules which handle any day
+use anyhow::Result;
+use enumflags2::{make_bitflags, BitFlags};
+use winnow::{ascii::dec_uint, combinator::separated, PResult, Parser as _};
+
+use crate::days::{Day, Part};
+
+/// A sum of numbers whose fast implementation forgets the numbers larger than 5, and whose part 2 is still a stub (like
+/// a day generated by `new`)
+pub struct Buggy;
+
+impl Day for Buggy {
+    const DAY: u32 = 3;
+
+    const TITLE: &'static str = "Buggy";
+
+    const PARTS: BitFlags<Part> = make_bitflags!(Part::One);
+
+    type Input = Vec<u64>;
+
+    type Params = ();
+
+    fn parser(input: &mut &str) -> PResult<Self::Input> {
+        separated(1.., dec_uint::<_, u64, _>, ' ').parse_next(input)
+    }
+
+    type Prepared = ();
+
+    type Output1 = u64;
+
+    fn part_1(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output1> {
+        Ok(input.iter().filter(|n| **n <= 5).sum())
+    }
+
+    type Output2 = usize;
+
+    fn part_2(_input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output2> {
+        Ok(0)
+    }
+
+    fn reference_part_1(input: &Self::Input) -> Option<Result<Self::Output1>> {
+        Some(Ok(input.iter().sum()))
+    }
+}
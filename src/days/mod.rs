@@ -1,67 +1,469 @@
-use std::{fmt::Display, fs, path::Path, time::Instant};
+use std::{
+    fmt, fs, io,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    str::FromStr,
+    time::Instant,
+};
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
+use enumflags2::{bitflags, BitFlags};
 use winnow::{PResult, Parser as _};
 
-pub mod day01;
-pub mod day02;
-pub mod day03;
-pub mod day04;
-pub mod day05;
-pub mod day06;
-pub mod day07;
-pub mod day08;
-pub mod day09;
-pub mod day10;
-pub mod day11;
-pub mod day12;
-pub mod day13;
-pub mod day14;
-pub mod day15;
-pub mod day16;
-pub mod day17;
-pub mod day18;
-pub mod day19;
-pub mod day20;
-pub mod day21;
-pub mod day22;
-pub mod day23;
-pub mod day24;
-pub mod day25;
+use crate::{
+    alloc,
+    answer::Answer,
+    bench::{bench_day, BenchOptions, DayBench},
+    diagnostics::ParseFailure,
+    differential::check_day,
+    report::{PartReport, RunReport},
+};
+
+// The `yYYYY` modules declaring the solution files of each year and their `SOLUTIONS`, and the `YEARS` registry, are
+// generated by the build script from the `src/days/yYYYY/dayNN.rs` files
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+#[cfg(test)]
+pub(crate) mod fixture;
+
+/// A part of a puzzle
+#[bitflags]
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    /// The number of the part (1 or 2)
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl TryFrom<u8> for Part {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            p => bail!("invalid part {p}"),
+        }
+    }
+}
+
+/// The value of a runtime parameter of a puzzle, given as `name=value`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Param {
+    pub name: String,
+    pub value: String,
+}
+
+impl FromStr for Param {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let Some((name, value)) = s.split_once('=') else {
+            bail!("expected a parameter as name=value, got {s}");
+        };
+        Ok(Self {
+            name: name.trim().to_string(),
+            value: value.trim().to_string(),
+        })
+    }
+}
+
+/// The runtime parameters of a puzzle which are not part of the input file (e.g. the size of the area)
+///
+/// They are a typed field of the parsed input, whose [`Default`] is the value for the real puzzle. The tests set the
+/// values of the examples directly, and `--param name=value` overrides them at runtime through [`Params::set`].
+pub trait Params: Default {
+    /// The names of the parameters, as given to [`Params::set`]
+    const NAMES: &'static [&'static str];
+
+    /// Override the parameter `name` with the textual `value`
+    fn set(&mut self, name: &str, value: &str) -> Result<()>;
+}
+
+/// Days without runtime parameters
+impl Params for () {
+    const NAMES: &'static [&'static str] = &[];
+
+    fn set(&mut self, name: &str, _value: &str) -> Result<()> {
+        bail!("unknown parameter {name}")
+    }
+}
 
 pub trait Day {
+    /// The day of the puzzle (1-25)
+    const DAY: u32;
+
+    /// The title of the puzzle
+    const TITLE: &'static str;
+
+    /// Some keywords describing the puzzle or the techniques used to solve it
+    const TAGS: &'static [&'static str] = &[];
+
+    /// Which parts of the puzzle are implemented
+    const PARTS: BitFlags<Part> = BitFlags::ALL;
+
     type Input;
 
+    /// The runtime parameters of the puzzle, see [`Params`]
+    ///
+    /// Days without parameters use `()`.
+    type Params: Params;
+
+    /// The runtime parameters stored in the parsed input, which are overridden by `--param`
+    ///
+    /// The default implementation returns `None`, for days without parameters.
+    fn params(_input: &mut Self::Input) -> Option<&mut Self::Params> {
+        None
+    }
+
     fn parser(input_string: &mut &str) -> PResult<Self::Input>;
 
-    type Output1: Display;
+    /// The result of the work shared by both parts, see [`Day::prepare`]
+    ///
+    /// Days without a preparation step use `()`.
+    type Prepared: Default;
+
+    /// Whether the day has a preparation step, which is then timed and reported
+    ///
+    /// It is derived from [`Day::Prepared`]: days without one use `()`, which has nothing to prepare.
+    const HAS_PREPARE: bool = size_of::<Self::Prepared>() != 0;
+
+    /// Do the expensive work needed by both parts once, before running them (e.g. finding the path of the race)
+    ///
+    /// The default implementation does nothing.
+    fn prepare(_input: &Self::Input) -> Result<Self::Prepared> {
+        Ok(Self::Prepared::default())
+    }
+
+    type Output1: Into<Answer>;
 
-    fn part_1(input: &Self::Input) -> Self::Output1;
+    /// Compute the answer to part 1, or an error if there is none (e.g. no path was found)
+    fn part_1(input: &Self::Input, prepared: &Self::Prepared) -> Result<Self::Output1>;
 
-    type Output2: Display;
+    type Output2: Into<Answer>;
 
-    fn part_2(input: &Self::Input) -> Self::Output2;
+    /// Compute the answer to part 2, or an error if there is none
+    fn part_2(input: &Self::Input, prepared: &Self::Prepared) -> Result<Self::Output2>;
 
-    fn parse_file(path: impl AsRef<Path>) -> Result<Self::Input> {
-        let input_string = fs::read_to_string(path).context("reading the input file")?;
-        let input = Self::parser
-            .parse(&input_string)
-            .map_err(|e| anyhow!(e.to_string()))
+    /// A slow but obviously correct implementation of part 1, which [`Day::part_1`] is tested against
+    ///
+    /// It returns an error for inputs which don't satisfy the assumptions of the puzzle, so that they can be skipped.
+    /// Days without a reference implementation return `None`.
+    fn reference_part_1(_input: &Self::Input) -> Option<Result<Self::Output1>> {
+        None
+    }
+
+    /// A slow but obviously correct implementation of part 2, see [`Day::reference_part_1`]
+    fn reference_part_2(_input: &Self::Input) -> Option<Result<Self::Output2>> {
+        None
+    }
+
+    /// Generate a random (but syntactically valid) input, e.g. to stress-test or benchmark the solution
+    ///
+    /// The same `seed` always gives the same input, and `size` scales it (its exact meaning depends on the day). Days
+    /// without a generator return `None`.
+    fn generate(_seed: u64, _size: usize) -> Option<String> {
+        None
+    }
+
+    /// Parse the input, then override its runtime parameters with `params`
+    fn parse_input(input_string: &str, params: &[Param]) -> Result<Self::Input> {
+        let mut input = Self::parser
+            .parse(input_string)
+            .map_err(|e| ParseFailure::new(input_string, &e))
             .context("running the parser")?;
+        for param in params {
+            let names = <Self::Params as Params>::NAMES;
+            let Some(day_params) =
+                Self::params(&mut input).filter(|_| names.contains(&param.name.as_str()))
+            else {
+                bail!(
+                    "day {} has no parameter {} (available: {})",
+                    Self::DAY,
+                    param.name,
+                    if names.is_empty() {
+                        "none".to_string()
+                    } else {
+                        names.join(", ")
+                    }
+                );
+            };
+            day_params
+                .set(&param.name, &param.value)
+                .with_context(|| format!("setting the parameter {}", param.name))?;
+        }
         Ok(input)
     }
 
-    #[allow(clippy::cast_precision_loss)]
-    fn run_day(path: impl AsRef<Path>) -> Result<()> {
+    fn parse_file(path: impl AsRef<Path>, params: &[Param]) -> Result<Self::Input> {
+        let input_string = read_input(path)?;
+        Self::parse_input(&input_string, params)
+    }
+
+    /// Compute the answer to one part of the puzzle, for the input file at `path`
+    fn solve(path: impl AsRef<Path>, part: Part) -> Result<Answer> {
+        if !Self::PARTS.contains(part) {
+            bail!("part {} is not implemented", part.number());
+        }
+        let input = catch_panic(|| Self::parse_file(path, &[]))?;
+        let prepared = catch_panic(|| Self::prepare(&input).context("preparing the input"))?;
+        catch_panic(|| match part {
+            Part::One => Ok(Self::part_1(&input, &prepared)?.into()),
+            Part::Two => Ok(Self::part_2(&input, &prepared)?.into()),
+        })
+    }
+
+    /// Parse the input file at `path` with the runtime parameters `params` and run the implemented parts, recording
+    /// answers and timings in `report`
+    ///
+    /// The preparation step is timed separately from the parts, if the day has one. A part which fails (or panics)
+    /// doesn't prevent the other one from running, its error is recorded in its report.
+    fn run_day(path: impl AsRef<Path>, params: &[Param], report: &mut RunReport) -> Result<()> {
         let before_parsing = Instant::now();
-        let input = Self::parse_file(path)?;
-        println!("Parsing took {:?}", before_parsing.elapsed());
-        let before_part1 = Instant::now();
-        println!("Part 1: {}", Self::part_1(&input));
-        println!("Part 1 took {:?}", before_part1.elapsed());
-        let before_part2 = Instant::now();
-        println!("Part 2: {}", Self::part_2(&input));
-        println!("Part 2 took {:?}", before_part2.elapsed());
+        let parse_alloc = alloc::Phase::start();
+        let input = catch_panic(|| Self::parse_file(path, params))?;
+        report.parse_time = Some(before_parsing.elapsed());
+        report.parse_alloc = parse_alloc.map(|phase| phase.stats());
+        let before_prepare = Instant::now();
+        let prepare_alloc = alloc::Phase::start();
+        let prepared = catch_panic(|| Self::prepare(&input).context("preparing the input"))?;
+        if Self::HAS_PREPARE {
+            report.prepare_time = Some(before_prepare.elapsed());
+            report.prepare_alloc = prepare_alloc.map(|phase| phase.stats());
+        }
+        if Self::PARTS.contains(Part::One) {
+            let before_part1 = Instant::now();
+            let part1_alloc = alloc::Phase::start();
+            let answer = catch_panic(|| Ok(Self::part_1(&input, &prepared)?.into()));
+            let time = before_part1.elapsed();
+            let mut part_report = PartReport::new(answer, time);
+            part_report.alloc = part1_alloc.map(|phase| phase.stats());
+            report.set_part(Part::One, part_report);
+        }
+        if Self::PARTS.contains(Part::Two) {
+            let before_part2 = Instant::now();
+            let part2_alloc = alloc::Phase::start();
+            let answer = catch_panic(|| Ok(Self::part_2(&input, &prepared)?.into()));
+            let time = before_part2.elapsed();
+            let mut part_report = PartReport::new(answer, time);
+            part_report.alloc = part2_alloc.map(|phase| phase.stats());
+            report.set_part(Part::Two, part_report);
+        }
+        Ok(())
+    }
+}
+
+/// A registered solution, with the metadata of its [`Day`] implementation
+pub struct Solution {
+    pub year: u32,
+    pub day: u32,
+    pub title: &'static str,
+    pub tags: &'static [&'static str],
+    pub parts: BitFlags<Part>,
+    pub params: &'static [&'static str],
+    run: fn(&Path, &[Param], &mut RunReport) -> Result<()>,
+    solve: fn(&Path, Part) -> Result<Answer>,
+    bench: fn(&str, &BenchOptions) -> Result<DayBench>,
+    generate: fn(u64, usize) -> Option<String>,
+    check: fn(u64, usize, &[String]) -> Result<usize>,
+}
+
+impl Solution {
+    /// Create the registry entry for a [`Day`] implementation
+    pub(crate) const fn new<D: Day>(year: u32) -> Self {
+        Self {
+            year,
+            day: D::DAY,
+            title: D::TITLE,
+            tags: D::TAGS,
+            parts: D::PARTS,
+            params: <D::Params as Params>::NAMES,
+            run: run_day::<D>,
+            solve: solve::<D>,
+            bench: bench_day::<D>,
+            generate: D::generate,
+            check: check_day::<D>,
+        }
+    }
+
+    /// Run the solution with the input file at `path` and the runtime parameters `params`
+    ///
+    /// Any error is recorded in the returned report.
+    pub fn run(&self, path: impl AsRef<Path>, params: &[Param]) -> RunReport {
+        let mut report = RunReport::new(self.year, self.day);
+        if let Err(e) = (self.run)(path.as_ref(), params, &mut report) {
+            report.error = Some(format!("{e:#}"));
+        }
+        report
+    }
+
+    /// Compute the answer to one part of the puzzle, for the input file at `path`
+    pub fn solve(&self, path: impl AsRef<Path>, part: Part) -> Result<Answer> {
+        (self.solve)(path.as_ref(), part)
+    }
+
+    /// Benchmark the parser, the preparation step and each implemented part separately, for the given input
+    pub fn bench(&self, input_string: &str, options: &BenchOptions) -> Result<DayBench> {
+        (self.bench)(input_string, options)
+    }
+
+    /// Generate a random input for this day, see [`Day::generate`]
+    pub fn generate(&self, seed: u64, size: usize) -> Result<String> {
+        let Some(input) = (self.generate)(seed, size) else {
+            bail!("no input generator for day {} of {}", self.day, self.year);
+        };
+        Ok(input)
+    }
+
+    /// Compare the parts with their reference implementations on generated inputs and mutated examples
+    ///
+    /// See [`check_day`] for the meaning of the arguments. Fails if the day has no reference implementation (or none of
+    /// the inputs could be compared).
+    pub fn check(&self, cases: u64, max_size: usize, examples: &[String]) -> Result<usize> {
+        let compared = (self.check)(cases, max_size, examples)?;
+        if compared == 0 {
+            bail!(
+                "no input could be compared for day {} of {}, does it have a reference implementation?",
+                self.day,
+                self.year
+            );
+        }
+        Ok(compared)
+    }
+}
+
+/// Call `f`, converting a panic into an error
+pub fn catch_panic<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("unknown cause");
+        Err(anyhow!("panicked: {message}"))
+    })
+}
+
+/// A row of the list of solutions, e.g. `2024 Day 14 ** Restroom Redoubt [simulation] (params: width, height)`
+impl fmt::Display for Solution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stars = "*".repeat(self.parts.len());
+        write!(
+            f,
+            "{} Day {:02} {stars:<2} {:<24} [{}]",
+            self.year,
+            self.day,
+            self.title,
+            self.tags.join(", ")
+        )?;
+        if !self.params.is_empty() {
+            write!(f, " (params: {})", self.params.join(", "))?;
+        }
         Ok(())
     }
 }
+
+/// The location of the input file for `day` of `year`, relative to the working directory
+pub fn input_path(year: u32, day: u32) -> String {
+    format!("inputs/{year}/day{day:02}.txt")
+}
+
+/// The location of the `example`-th example file (starting at 1) for `day` of `year`
+pub fn example_path(year: u32, day: u32, example: usize) -> String {
+    format!("inputs/{year}/examples/day{day:02}-{example}.txt")
+}
+
+/// The location of the cached puzzle page for `day` of `year`
+pub fn puzzle_path(year: u32, day: u32) -> String {
+    format!("inputs/puzzles/{year}/day{day:02}.html")
+}
+
+/// Read the input file at `path`, or the standard input if `path` is `-`
+pub fn read_input(path: impl AsRef<Path>) -> Result<String> {
+    let path = path.as_ref();
+    if path == Path::new("-") {
+        return io::read_to_string(io::stdin()).context("reading the standard input");
+    }
+    fs::read_to_string(path).with_context(|| format!("reading the input file {}", path.display()))
+}
+
+/// Read the example files for `day` of `year`, in order, until one is missing
+pub fn read_examples(year: u32, day: u32) -> Result<Vec<String>> {
+    (1..)
+        .map(|k| example_path(year, day, k))
+        .take_while(|path| fs::exists(path).unwrap_or_default())
+        .map(read_input)
+        .collect()
+}
+
+/// Type-erased entry point for [`Day::run_day`]
+fn run_day<D: Day>(path: &Path, params: &[Param], report: &mut RunReport) -> Result<()> {
+    D::run_day(path, params, report)
+}
+
+/// Type-erased entry point for [`Day::solve`]
+fn solve<D: Day>(path: &Path, part: Part) -> Result<Answer> {
+    D::solve(path, part)
+}
+
+/// Iterate over all the registered solutions, ordered by year and day
+pub fn all_solutions() -> impl Iterator<Item = &'static Solution> {
+    YEARS.iter().copied().flatten()
+}
+
+/// The latest year with registered solutions, if any
+pub fn latest_year() -> Option<u32> {
+    all_solutions().last().map(|s| s.year)
+}
+
+/// Iterate over the solutions registered for `year`
+pub fn solutions(year: u32) -> impl Iterator<Item = &'static Solution> {
+    all_solutions().filter(move |s| s.year == year)
+}
+
+/// Retrieve the solution registered for `day` of `year`
+pub fn get_solution(year: u32, day: u32) -> Result<&'static Solution> {
+    let Some(solution) = solutions(year).find(|s| s.day == day) else {
+        bail!("no solution registered for day {day} of {year}");
+    };
+    Ok(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catch_panic() {
+        assert_eq!(catch_panic(|| Ok(42)).unwrap(), 42);
+        assert_eq!(
+            catch_panic::<()>(|| Err(anyhow!("no solution")))
+                .unwrap_err()
+                .to_string(),
+            "no solution"
+        );
+        assert_eq!(
+            catch_panic::<()>(|| panic!("index out of bounds"))
+                .unwrap_err()
+                .to_string(),
+            "panicked: index out of bounds"
+        );
+        let index = 3;
+        assert_eq!(
+            catch_panic::<()>(|| panic!("index {index} out of bounds"))
+                .unwrap_err()
+                .to_string(),
+            "panicked: index 3 out of bounds"
+        );
+    }
+}
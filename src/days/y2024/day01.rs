@@ -1,7 +1,9 @@
+use anyhow::Result;
 use itertools::Itertools;
 use winnow::{
     ascii::{digit1, newline, space1},
     combinator::{separated, separated_pair},
+    error::StrContext,
     PResult, Parser as _,
 };
 
@@ -15,41 +17,72 @@ pub struct Numbers {
     b: Vec<u32>,
 }
 
+impl Numbers {
+    /// Create the lists from the left and right columns
+    pub fn new(a: Vec<u32>, b: Vec<u32>) -> Self {
+        Self { a, b }
+    }
+
+    /// The left column
+    pub fn left(&self) -> &[u32] {
+        &self.a
+    }
+
+    /// The right column
+    pub fn right(&self) -> &[u32] {
+        &self.b
+    }
+}
+
 fn parse_line(input: &mut &str) -> PResult<(u32, u32)> {
-    separated_pair(digit1.parse_to(), space1, digit1.parse_to()).parse_next(input)
+    separated_pair(digit1.parse_to(), space1, digit1.parse_to())
+        .context(StrContext::Label("location IDs"))
+        .parse_next(input)
 }
 
 impl Day for Day01 {
+    const DAY: u32 = 1;
+
+    const TITLE: &'static str = "Historian Hysteria";
+
+    const TAGS: &'static [&'static str] = &["sorting", "counting"];
+
     type Input = Numbers;
 
+    type Params = ();
+
     fn parser(input: &mut &str) -> PResult<Self::Input> {
-        let lines: Vec<_> = separated(1.., parse_line, newline).parse_next(input)?;
+        let lines: Vec<_> = separated(1.., parse_line, newline)
+            .context(StrContext::Label("lists"))
+            .parse_next(input)?;
         let (a, b) = lines.into_iter().unzip();
         Ok(Numbers { a, b })
     }
 
+    type Prepared = ();
+
     type Output1 = usize;
 
     /// Part 1 took 20.008us
-    fn part_1(input: &Self::Input) -> Self::Output1 {
-        input
+    fn part_1(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output1> {
+        Ok(input
             .a
             .iter()
             .sorted_unstable()
             .zip(input.b.iter().sorted_unstable())
             .map(|(a, b)| a.abs_diff(*b) as usize)
-            .sum()
+            .sum())
     }
 
     type Output2 = usize;
 
     /// Part 2 took 36.35us
-    fn part_2(input: &Self::Input) -> Self::Output2 {
+    fn part_2(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output2> {
         let counts = input.b.iter().counts();
-        input
+        Ok(input
             .a
             .iter()
             .map(|a| *a as usize * counts.get(a).copied().unwrap_or(0))
-            .sum()
+            .sum())
     }
 }
@@ -1,7 +1,9 @@
+use anyhow::Result;
 use itertools::Itertools;
 use winnow::{
     ascii::{digit1, line_ending},
     combinator::separated,
+    error::StrContext,
     PResult, Parser as _,
 };
 
@@ -44,35 +46,49 @@ impl Diffs {
 }
 
 fn parse_report(input: &mut &str) -> PResult<Report> {
-    let values: Vec<_> = separated(1.., digit1.parse_to::<i16>(), ' ').parse_next(input)?;
+    let values: Vec<_> = separated(1.., digit1.parse_to::<i16>(), ' ')
+        .context(StrContext::Label("report"))
+        .parse_next(input)?;
     Ok(Report(values))
 }
 
 impl Day for Day02 {
+    const DAY: u32 = 2;
+
+    const TITLE: &'static str = "Red-Nosed Reports";
+
+    const TAGS: &'static [&'static str] = &["sequences"];
+
     type Input = Vec<Report>;
 
+    type Params = ();
+
     fn parser(input: &mut &str) -> PResult<Self::Input> {
-        separated(1.., parse_report, line_ending).parse_next(input)
+        separated(1.., parse_report, line_ending)
+            .context(StrContext::Label("reports"))
+            .parse_next(input)
     }
 
+    type Prepared = ();
+
     type Output1 = usize;
 
     /// Part 1 took 28.03us
-    fn part_1(input: &Self::Input) -> Self::Output1 {
-        input
+    fn part_1(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output1> {
+        Ok(input
             .iter()
             .filter(|report| {
                 let diffs = report.diffs();
                 diffs.is_increasing() || diffs.is_decreasing()
             })
-            .count()
+            .count())
     }
 
     type Output2 = usize;
 
     /// Part 2 took 191.54us
-    fn part_2(input: &Self::Input) -> Self::Output2 {
-        input
+    fn part_2(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output2> {
+        Ok(input
             .iter()
             .filter(|report| {
                 report
@@ -80,6 +96,6 @@ impl Day for Day02 {
                     .iter()
                     .any(|diffs| diffs.is_increasing() || diffs.is_decreasing())
             })
-            .count()
+            .count())
     }
 }
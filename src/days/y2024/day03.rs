@@ -1,6 +1,8 @@
+use anyhow::Result;
 use winnow::{
     ascii::digit1,
     combinator::{alt, delimited, repeat, repeat_till, separated_pair, terminated},
+    error::StrContext,
     token::any,
     PResult, Parser as _,
 };
@@ -24,32 +26,48 @@ fn parse_mul(input: &mut &str) -> PResult<Instr> {
         separated_pair(digit1.parse_to(), ',', digit1.parse_to()),
         ')',
     )
+    .context(StrContext::Label("mul instruction"))
     .parse_next(input)?;
     Ok(Instr::Mul { x, y })
 }
 
 /// Parser for "do" instructions
 fn parse_do(input: &mut &str) -> PResult<Instr> {
-    "do()".map(|_| Instr::Do).parse_next(input)
+    "do()"
+        .map(|_| Instr::Do)
+        .context(StrContext::Label("do instruction"))
+        .parse_next(input)
 }
 
 /// Parser for "don't" instructions
 fn parse_dont(input: &mut &str) -> PResult<Instr> {
-    "don't()".map(|_| Instr::Dont).parse_next(input)
+    "don't()"
+        .map(|_| Instr::Dont)
+        .context(StrContext::Label("don't instruction"))
+        .parse_next(input)
 }
 
 /// Parser for a sequence of garbage bytes followed by an instruction
 ///
 /// This parser consumes but ignores the garbage portion.
 fn parse_instr(input: &mut &str) -> PResult<Instr> {
-    let ((), instr) =
-        repeat_till(0.., any, alt((parse_mul, parse_do, parse_dont))).parse_next(input)?;
+    let ((), instr) = repeat_till(0.., any, alt((parse_mul, parse_do, parse_dont)))
+        .context(StrContext::Label("instruction"))
+        .parse_next(input)?;
     Ok(instr)
 }
 
 impl Day for Day03 {
+    const DAY: u32 = 3;
+
+    const TITLE: &'static str = "Mull It Over";
+
+    const TAGS: &'static [&'static str] = &["parsing"];
+
     type Input = Vec<Instr>;
 
+    type Params = ();
+
     /// Parsing took 119.5us
     ///
     /// Could also be done with a regex: (?:mul\((\d+),(\d+)\)|(do(?:n't)?\(\)).*?)+?
@@ -58,26 +76,29 @@ impl Day for Day03 {
             repeat(1.., parse_instr),
             repeat::<_, _, (), _, _>(0.., any), // there could be garbage after the last instruction
         )
+        .context(StrContext::Label("memory"))
         .parse_next(input)
     }
 
+    type Prepared = ();
+
     type Output1 = u64;
 
     /// Part 1 took 1.1us
-    fn part_1(input: &Self::Input) -> Self::Output1 {
-        input
+    fn part_1(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output1> {
+        Ok(input
             .iter()
             .filter_map(|m| match m {
                 Instr::Mul { x, y } => Some(x * y),
                 _ => None,
             })
-            .sum()
+            .sum())
     }
 
     type Output2 = u64;
 
     /// Part 2 took 2.67us
-    fn part_2(input: &Self::Input) -> Self::Output2 {
+    fn part_2(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output2> {
         let mut accumulate = true;
         let mut sum = 0;
         for instr in input {
@@ -91,6 +112,6 @@ impl Day for Day03 {
                 Instr::Dont => accumulate = false,
             }
         }
-        sum
+        Ok(sum)
     }
 }
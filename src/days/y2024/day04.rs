@@ -1,7 +1,9 @@
+use anyhow::Result;
 use itertools::Itertools;
 use winnow::{
     ascii::line_ending,
     combinator::{repeat, separated},
+    error::StrContext,
     token::one_of,
     PResult, Parser as _,
 };
@@ -85,25 +87,39 @@ impl Grid {
 
 /// Parse a line of the input file into a [`Row`].
 fn parse_row(input: &mut &str) -> PResult<Row> {
-    let letters = repeat(1.., one_of('A'..='z')).parse_next(input)?;
+    let letters = repeat(1.., one_of('A'..='z'))
+        .context(StrContext::Label("row"))
+        .parse_next(input)?;
     Ok(Row { cells: letters })
 }
 
 impl Day for Day04 {
+    const DAY: u32 = 4;
+
+    const TITLE: &'static str = "Ceres Search";
+
+    const TAGS: &'static [&'static str] = &["grid", "word-search"];
+
     type Input = Grid;
 
+    type Params = ();
+
     /// Parser took 119.7us
     fn parser(input: &mut &str) -> PResult<Self::Input> {
-        let rows = separated(1.., parse_row, line_ending).parse_next(input)?;
+        let rows = separated(1.., parse_row, line_ending)
+            .context(StrContext::Label("word search"))
+            .parse_next(input)?;
         Ok(Grid { rows })
     }
 
+    type Prepared = ();
+
     type Output1 = usize;
 
     /// Part 1 took 322.3us
-    fn part_1(grid: &Self::Input) -> Self::Output1 {
+    fn part_1(grid: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output1> {
         // find a grid cell which contains the first letter of the word, and then search in all directions
-        (0..GRID_SIZE)
+        Ok((0..GRID_SIZE)
             .cartesian_product(0..GRID_SIZE)
             .filter_map(|(x, y)| {
                 if grid.is_letter(x, y, &WORD[0]) == Some(true) {
@@ -112,18 +128,18 @@ impl Day for Day04 {
                     None
                 }
             })
-            .sum()
+            .sum())
     }
 
     type Output2 = usize;
 
     /// Part 2 took 115.8us
-    fn part_2(grid: &Self::Input) -> Self::Output2 {
-        (0..GRID_SIZE)
+    fn part_2(grid: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output2> {
+        Ok((0..GRID_SIZE)
             .cartesian_product(0..GRID_SIZE)
             .filter(|(x, y)| {
                 grid.is_letter(*x, *y, &'A') == Some(true) && grid.search_cross(*x, *y)
             })
-            .count()
+            .count())
     }
 }
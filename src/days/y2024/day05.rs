@@ -1,8 +1,10 @@
 use std::cmp::Ordering;
 
+use anyhow::Result;
 use winnow::{
     ascii::{digit1, line_ending},
     combinator::{separated, separated_pair},
+    error::StrContext,
     PResult, Parser as _,
 };
 
@@ -27,23 +29,31 @@ pub struct Puzzle {
 
 /// Parse a single rule containing two page numbers defining their ordering
 fn parse_rule(input: &mut &str) -> PResult<(u8, u8)> {
-    separated_pair(digit1.parse_to(), '|', digit1.parse_to()).parse_next(input)
+    separated_pair(digit1.parse_to(), '|', digit1.parse_to())
+        .context(StrContext::Label("page ordering rule"))
+        .parse_next(input)
 }
 
 /// Parse all the rules into a hashset of (first, second) page tuples
 fn parse_rules(input: &mut &str) -> PResult<HashSet<(u8, u8)>> {
-    separated(1.., parse_rule, line_ending).parse_next(input)
+    separated(1.., parse_rule, line_ending)
+        .context(StrContext::Label("page ordering rules"))
+        .parse_next(input)
 }
 
 /// Parse an update definition (a list of pages)
 fn parse_update(input: &mut &str) -> PResult<Update> {
-    let pages = separated(1.., digit1.parse_to::<u8>(), ',').parse_next(input)?;
+    let pages = separated(1.., digit1.parse_to::<u8>(), ',')
+        .context(StrContext::Label("update"))
+        .parse_next(input)?;
     Ok(Update { pages })
 }
 
 /// Parse all updates
 fn parse_updates(input: &mut &str) -> PResult<Vec<Update>> {
-    separated(1.., parse_update, line_ending).parse_next(input)
+    separated(1.., parse_update, line_ending)
+        .context(StrContext::Label("updates"))
+        .parse_next(input)
 }
 
 /// Compare function for two page numbers
@@ -58,21 +68,32 @@ fn compare_order(a: &u8, b: &u8, rules: &HashSet<(u8, u8)>) -> Ordering {
 }
 
 impl Day for Day05 {
+    const DAY: u32 = 5;
+
+    const TITLE: &'static str = "Print Queue";
+
+    const TAGS: &'static [&'static str] = &["sorting", "ordering"];
+
     type Input = Puzzle;
 
+    type Params = ();
+
     /// Parsing took 91.3us
     fn parser(input: &mut &str) -> PResult<Self::Input> {
         let (rules, updates) =
             separated_pair(parse_rules, (line_ending, line_ending), parse_updates)
+                .context(StrContext::Label("manual"))
                 .parse_next(input)?;
         Ok(Puzzle { rules, updates })
     }
 
+    type Prepared = ();
+
     type Output1 = usize;
 
     /// Part 1 took 6.6us
-    fn part_1(input: &Self::Input) -> Self::Output1 {
-        input
+    fn part_1(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output1> {
+        Ok(input
             .updates
             .iter()
             .filter(|u| {
@@ -81,14 +102,14 @@ impl Day for Day05 {
                 })
             })
             .map(|u| *(u.pages.get(u.pages.len() / 2).unwrap()) as usize)
-            .sum()
+            .sum())
     }
 
     type Output2 = usize;
 
     /// Part 2 took 49.5us
-    fn part_2(input: &Self::Input) -> Self::Output2 {
-        input
+    fn part_2(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output2> {
+        Ok(input
             .updates
             .iter()
             .filter_map(|u| {
@@ -100,7 +121,7 @@ impl Day for Day05 {
                     None
                 }
             })
-            .sum()
+            .sum())
     }
 }
 
@@ -141,6 +162,6 @@ mod tests {
     #[test]
     fn test_part2() {
         let parsed = Day05::parser(&mut INPUT).unwrap();
-        assert_eq!(Day05::part_2(&parsed), 123);
+        assert_eq!(Day05::part_2(&parsed, &()).unwrap(), 123);
     }
 }
@@ -1,8 +1,10 @@
+use anyhow::Result;
 use enumflags2::{bitflags, BitFlags};
 use rayon::iter::{ParallelBridge, ParallelIterator as _};
 use winnow::{
     ascii::line_ending,
     combinator::{repeat, separated},
+    error::StrContext,
     token::one_of,
     PResult, Parser as _,
 };
@@ -166,7 +168,9 @@ impl State {
 
 /// Parse a row of the grid, returning the x coordinate and symbol of each non-empty tile
 fn parse_line(input: &mut &str) -> PResult<Vec<(usize, char)>> {
-    let cells: Vec<_> = repeat(1.., one_of(('.', '#', '^', '>', 'v', '<'))).parse_next(input)?;
+    let cells: Vec<_> = repeat(1.., one_of(('.', '#', '^', '>', 'v', '<')))
+        .context(StrContext::Label("row"))
+        .parse_next(input)?;
     Ok(cells
         .into_iter()
         .enumerate()
@@ -175,14 +179,24 @@ fn parse_line(input: &mut &str) -> PResult<Vec<(usize, char)>> {
 }
 
 impl Day for Day06 {
+    const DAY: u32 = 6;
+
+    const TITLE: &'static str = "Guard Gallivant";
+
+    const TAGS: &'static [&'static str] = &["grid", "simulation", "parallel"];
+
     type Input = State;
 
+    type Params = ();
+
     /// Parse the puzzle input into a [`State`]
     ///
     /// Parsing took 98.022us
     fn parser(input: &mut &str) -> PResult<Self::Input> {
         let mut puzzle = State::default();
-        let lines: Vec<_> = separated(1.., parse_line, line_ending).parse_next(input)?;
+        let lines: Vec<_> = separated(1.., parse_line, line_ending)
+            .context(StrContext::Label("map"))
+            .parse_next(input)?;
         for (y, line) in lines.into_iter().enumerate() {
             for (x, cell) in line {
                 match cell {
@@ -227,32 +241,34 @@ impl Day for Day06 {
         Ok(puzzle)
     }
 
+    type Prepared = ();
+
     type Output1 = usize;
 
     /// Part 1 took 199.86us
-    fn part_1(input: &Self::Input) -> Self::Output1 {
+    fn part_1(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output1> {
         let mut state = input.clone();
         // advance the guard until it exits the area
         while let Some(true) = state.advance() {}
         // return how many tiles were visited
-        state.visited.len()
+        Ok(state.visited.len())
     }
 
     type Output2 = usize;
 
     /// Part 2 took 25.43ms
-    fn part_2(input: &Self::Input) -> Self::Output2 {
+    fn part_2(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output2> {
         let mut state = input.clone();
         // advance the guard until it exits the area to update the list of visited tiles
         while let Some(true) = state.advance() {}
         // for each visited tile, try to replace it with an obstacle and see if the guard enters a loop in that case
         // note that no obstacle can be placed at the guard's starting location
-        state
+        Ok(state
             .visited
             .keys()
             .par_bridge()
             .filter(|&pos| pos != &state.init_pos && input.loops_with_obstacle(*pos))
-            .count()
+            .count())
     }
 }
 
@@ -275,6 +291,6 @@ mod tests {
     #[test]
     fn test_part2() {
         let parsed = Day06::parser(&mut INPUT).unwrap();
-        assert_eq!(Day06::part_2(&parsed), 6);
+        assert_eq!(Day06::part_2(&parsed, &()).unwrap(), 6);
     }
 }
@@ -1,7 +1,9 @@
+use anyhow::Result;
 use itertools::Itertools;
 use winnow::{
     ascii::{digit1, line_ending},
     combinator::{separated, separated_pair},
+    error::StrContext,
     PResult, Parser as _,
 };
 
@@ -24,13 +26,16 @@ pub enum Operator {
 
 /// Parse a list of operands separated by spaces
 fn parse_operands(input: &mut &str) -> PResult<Vec<u64>> {
-    separated(1.., digit1.parse_to::<u64>(), ' ').parse_next(input)
+    separated(1.., digit1.parse_to::<u64>(), ' ')
+        .context(StrContext::Label("operands"))
+        .parse_next(input)
 }
 
 /// Parse a line which consists of a result and operands separated by a colon and space
 fn parse_line(input: &mut &str) -> PResult<Line> {
-    let (result, operands) =
-        separated_pair(digit1.parse_to::<u64>(), ": ", parse_operands).parse_next(input)?;
+    let (result, operands) = separated_pair(digit1.parse_to::<u64>(), ": ", parse_operands)
+        .context(StrContext::Label("equation"))
+        .parse_next(input)?;
     Ok(Line { result, operands })
 }
 
@@ -61,17 +66,29 @@ fn try_operators(result: u64, operands: &[u64], operators: &[Operator]) -> bool
 }
 
 impl Day for Day07 {
+    const DAY: u32 = 7;
+
+    const TITLE: &'static str = "Bridge Repair";
+
+    const TAGS: &'static [&'static str] = &["combinatorics", "brute-force"];
+
     type Input = Vec<Line>;
 
+    type Params = ();
+
     fn parser(input: &mut &str) -> PResult<Self::Input> {
-        separated(1.., parse_line, line_ending).parse_next(input)
+        separated(1.., parse_line, line_ending)
+            .context(StrContext::Label("equations"))
+            .parse_next(input)
     }
 
+    type Prepared = ();
+
     type Output1 = u64;
 
     /// Part 1 took 7.96ms
-    fn part_1(input: &Self::Input) -> Self::Output1 {
-        input
+    fn part_1(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output1> {
+        Ok(input
             .iter()
             .filter_map(|l| {
                 if try_operators(l.result, &l.operands, &[Operator::Add, Operator::Mul]) {
@@ -80,14 +97,14 @@ impl Day for Day07 {
                     None
                 }
             })
-            .sum()
+            .sum())
     }
 
     type Output2 = u64;
 
     /// Part 2 took 321.1ms
-    fn part_2(input: &Self::Input) -> Self::Output2 {
-        input
+    fn part_2(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output2> {
+        Ok(input
             .iter()
             .filter_map(|l| {
                 if try_operators(
@@ -100,7 +117,7 @@ impl Day for Day07 {
                     None
                 }
             })
-            .sum()
+            .sum())
     }
 }
 
@@ -122,12 +139,12 @@ mod tests {
     #[test]
     fn test_part1() {
         let parsed = Day07::parser(&mut INPUT).unwrap();
-        assert_eq!(Day07::part_1(&parsed), 3749);
+        assert_eq!(Day07::part_1(&parsed, &()).unwrap(), 3749);
     }
 
     #[test]
     fn test_part2() {
         let parsed = Day07::parser(&mut INPUT).unwrap();
-        assert_eq!(Day07::part_2(&parsed), 11387);
+        assert_eq!(Day07::part_2(&parsed, &()).unwrap(), 11387);
     }
 }
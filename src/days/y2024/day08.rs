@@ -0,0 +1,165 @@
+use ahash::HashSetExt;
+use anyhow::Result;
+use itertools::Itertools;
+use winnow::{
+    ascii::line_ending,
+    combinator::{repeat, separated},
+    error::StrContext,
+    token::none_of,
+    PResult, Parser as _,
+};
+
+use crate::days::Day;
+
+pub type HashSet<K> = std::collections::HashSet<K, ahash::RandomState>;
+pub type HashMap<K, V> = std::collections::HashMap<K, V, ahash::RandomState>;
+
+pub struct Day08;
+
+/// An antenna's position
+#[derive(Debug)]
+pub struct Antenna {
+    x: isize,
+    y: isize,
+}
+
+/// Parse a row of the grid
+fn parse_row(input: &mut &str) -> PResult<Vec<char>> {
+    repeat(1.., none_of(['\n']))
+        .context(StrContext::Label("row"))
+        .parse_next(input)
+}
+
+/// The antennae grouped by frequency, and the dimensions of the grid
+#[derive(Debug)]
+pub struct Antennae {
+    by_frequency: HashMap<char, Vec<Antenna>>,
+    width: isize,
+    height: isize,
+}
+
+/// How many steps of `delta` fit in `size`, unbounded if the position doesn't change along that axis
+fn steps(size: isize, delta: isize) -> isize {
+    size.checked_div(delta.abs()).unwrap_or(isize::MAX)
+}
+
+/// Get the antinodes, knowing the list of antennae grouped by frequency
+fn get_antinodes(antennae: &Antennae, part1: bool) -> HashSet<(isize, isize)> {
+    let (width, height) = (antennae.width, antennae.height);
+    let mut antinodes = HashSet::new();
+    for list in antennae.by_frequency.values() {
+        antinodes.extend(
+            list.iter()
+                .tuple_combinations()
+                .flat_map(|(a, b)| {
+                    let mut res = Vec::new();
+                    let dx = a.x - b.x;
+                    let dy = a.y - b.y;
+                    let (start, limit) = if part1 {
+                        (1, 2)
+                    } else {
+                        (0, steps(width, dx).min(steps(height, dy)))
+                    };
+                    for i in start..limit {
+                        res.push((a.x + i * dx, a.y + i * dy));
+                        res.push((b.x - i * dx, b.y - i * dy));
+                    }
+                    res
+                })
+                .filter(|(ax, ay)| (0..width).contains(ax) && (0..height).contains(ay)),
+        );
+    }
+    antinodes
+}
+
+impl Day for Day08 {
+    const DAY: u32 = 8;
+
+    const TITLE: &'static str = "Resonant Collinearity";
+
+    const TAGS: &'static [&'static str] = &["grid", "geometry"];
+
+    type Input = Antennae;
+
+    type Params = ();
+
+    /// Parsing took 34.2us
+    fn parser(input: &mut &str) -> PResult<Self::Input> {
+        let rows: Vec<_> = separated(1.., parse_row, line_ending)
+            .context(StrContext::Label("antenna map"))
+            .parse_next(input)?;
+        let width = rows.iter().map(Vec::len).max().unwrap_or_default() as isize;
+        let height = rows.len() as isize;
+        let mut out = HashMap::<char, Vec<Antenna>>::default();
+        for (y, row) in rows.into_iter().enumerate() {
+            for (x, symbol) in row.into_iter().enumerate().filter(|(_, s)| *s != '.') {
+                let antenna = Antenna {
+                    x: x as isize,
+                    y: y as isize,
+                };
+                if let Some(antennae) = out.get_mut(&symbol) {
+                    antennae.push(antenna);
+                } else {
+                    out.insert(symbol, vec![antenna]);
+                }
+            }
+        }
+        Ok(Antennae {
+            by_frequency: out,
+            width,
+            height,
+        })
+    }
+
+    type Prepared = ();
+
+    type Output1 = usize;
+
+    /// Part 1 took 16.33us
+    fn part_1(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output1> {
+        Ok(get_antinodes(input, true).len())
+    }
+
+    type Output2 = usize;
+
+    /// Part 2 took 61.9us
+    fn part_2(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output2> {
+        Ok(get_antinodes(input, false).len())
+    }
+}
+
+#[cfg(test)]
+#[allow(const_item_mutation)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............";
+
+    #[test]
+    fn test_part2() {
+        let parsed = Day08::parser(&mut INPUT).unwrap();
+        assert_eq!(Day08::part_2(&parsed, &()).unwrap(), 34);
+    }
+
+    #[test]
+    fn test_wide_grid() {
+        let mut input = format!("A{}A\n", ".".repeat(198));
+        input.push_str(&format!("{}B{}\n", ".".repeat(5), ".".repeat(194)));
+        input.push_str(&format!("{}B{}", ".".repeat(5), ".".repeat(194)));
+        let parsed = Day08::parser(&mut input.as_str()).unwrap();
+        assert_eq!((parsed.width, parsed.height), (200, 3));
+        assert_eq!(Day08::part_1(&parsed, &()).unwrap(), 1);
+        assert_eq!(Day08::part_2(&parsed, &()).unwrap(), 5);
+    }
+}
@@ -1,5 +1,6 @@
+use anyhow::{bail, Result};
 use itertools::repeat_n;
-use winnow::{combinator::repeat, token::any, PResult, Parser as _};
+use winnow::{combinator::repeat, error::StrContext, token::one_of, PResult, Parser as _};
 
 use crate::days::Day;
 
@@ -23,7 +24,7 @@ pub struct MapIterator {
 
 impl MapIterator {
     fn is_file(&self, pos: usize) -> bool {
-        pos % 2 == 0
+        pos.is_multiple_of(2)
     }
 }
 
@@ -31,6 +32,10 @@ impl Iterator for MapIterator {
     type Item = usize; // block ID
 
     fn next(&mut self) -> Option<Self::Item> {
+        // the head moved past the last unmoved file (e.g. when the file after an empty space was moved entirely into it)
+        if self.pos_head > self.pos_tail {
+            return None;
+        }
         match (
             self.is_file(self.pos_head),
             self.remaining_head,
@@ -109,8 +114,12 @@ impl IntoIterator for DiskMap {
         let first = *self.0.first().unwrap();
         let len = self.0.len();
         assert!(len % 2 == 1); // disk map should end with a file
+
+        // add an empty space of size 0 at the end, so that the head can always move past the last file
+        let mut map = self.0;
+        map.push(0);
         Self::IntoIter {
-            map: self.0,
+            map,
             pos_head: 0,
             pos_tail: len - 1,
             remaining_head: first,
@@ -118,35 +127,89 @@ impl IntoIterator for DiskMap {
     }
 }
 
+impl DiskMap {
+    /// Expand the disk map into the list of its blocks, with the ID of the file they contain (or `None` if empty)
+    ///
+    /// Fails if the map doesn't respect the assumptions of the puzzle (no empty file, and ending with a file).
+    fn blocks(&self) -> Result<Vec<Option<usize>>> {
+        if self.0.len().is_multiple_of(2) {
+            bail!("the disk map should end with a file");
+        }
+        if self.0.iter().step_by(2).any(|size| *size == 0) {
+            bail!("the disk map should not contain empty files");
+        }
+        Ok(self
+            .0
+            .iter()
+            .enumerate()
+            .flat_map(|(i, size)| repeat_n((i % 2 == 0).then_some(i / 2), *size as usize))
+            .collect())
+    }
+}
+
+/// The checksum of the blocks of a disk
+fn checksum(blocks: &[Option<usize>]) -> usize {
+    blocks
+        .iter()
+        .enumerate()
+        .map(|(i, id)| i * id.unwrap_or_default())
+        .sum()
+}
+
+/// Generate a random disk map with `size` files
+///
+/// Files have between 1 and 9 blocks, and the empty spaces between them between 0 and 9 blocks.
+fn generate_disk_map(rng: &mut fastrand::Rng, size: usize) -> String {
+    (0..size.max(1) * 2 - 1)
+        .map(|i| {
+            if i % 2 == 0 {
+                rng.char('1'..='9')
+            } else {
+                rng.char('0'..='9')
+            }
+        })
+        .collect()
+}
+
 impl Day for Day09 {
+    const DAY: u32 = 9;
+
+    const TITLE: &'static str = "Disk Fragmenter";
+
+    const TAGS: &'static [&'static str] = &["iterator", "compaction"];
+
     type Input = DiskMap;
 
+    type Params = ();
+
     fn parser(input: &mut &str) -> PResult<Self::Input> {
-        let chars: Vec<char> = repeat(1.., any).parse_next(input)?;
-        Ok(DiskMap(
-            chars
-                .into_iter()
-                .map(|c| c.to_digit(10).unwrap() as u8)
-                .collect(),
-        ))
+        repeat(
+            1..,
+            one_of('0'..='9').map(|c: char| c.to_digit(10).unwrap() as u8),
+        )
+        .map(DiskMap)
+        .context(StrContext::Label("disk map"))
+        .parse_next(input)
     }
 
+    type Prepared = ();
+
     type Output1 = usize;
 
     /// Part 1 took 136.8us
-    fn part_1(input: &Self::Input) -> Self::Output1 {
-        input
+    fn part_1(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output1> {
+        Ok(input
             .clone()
             .into_iter()
             .enumerate()
             .map(|(i, id)| i * id)
-            .sum()
+            .sum())
     }
 
     type Output2 = usize;
 
     /// Part 2 took 197.7ms
-    fn part_2(input: &Self::Input) -> Self::Output2 {
+    fn part_2(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output2> {
         // create the actual sectors list for the map
         // `None` means the sector is empty, `Some(id)` means it contains a part of file ID
         let mut out: Vec<_> = input
@@ -211,11 +274,50 @@ impl Day for Day09 {
             // check the next file in descending order
             i = i.saturating_sub(file_size); // avoid underflow
         }
-        // checksum
-        out.into_iter()
-            .enumerate()
-            .map(|(i, id)| i * id.unwrap_or_default())
-            .sum()
+        Ok(checksum(&out))
+    }
+
+    /// Move the blocks one at a time, from the last one to the first empty block
+    fn reference_part_1(input: &Self::Input) -> Option<Result<Self::Output1>> {
+        Some(input.blocks().map(|mut blocks| {
+            while let (Some(empty), Some(last)) = (
+                blocks.iter().position(Option::is_none),
+                blocks.iter().rposition(Option::is_some),
+            ) {
+                if empty > last {
+                    break;
+                }
+                blocks.swap(empty, last);
+            }
+            checksum(&blocks)
+        }))
+    }
+
+    /// Move each file once, in decreasing order of ID, to the first span of empty blocks on its left which can hold it
+    fn reference_part_2(input: &Self::Input) -> Option<Result<Self::Output2>> {
+        Some(input.blocks().map(|mut blocks| {
+            let files = input.0.len().div_ceil(2);
+            for id in (0..files).rev() {
+                let start = blocks.iter().position(|b| *b == Some(id)).unwrap();
+                let size = input.0[id * 2] as usize;
+                let target = (0..start).find(|i| {
+                    blocks
+                        .get(*i..*i + size)
+                        .is_some_and(|span| span.iter().all(Option::is_none))
+                });
+                if let Some(target) = target {
+                    for k in 0..size {
+                        blocks.swap(target + k, start + k);
+                    }
+                }
+            }
+            checksum(&blocks)
+        }))
+    }
+
+    /// Generate a disk map with `size` files
+    fn generate(seed: u64, size: usize) -> Option<String> {
+        Some(generate_disk_map(&mut fastrand::Rng::with_seed(seed), size))
     }
 }
 
@@ -223,18 +325,50 @@ impl Day for Day09 {
 #[allow(const_item_mutation)]
 mod tests {
     use super::*;
+    use crate::{diagnostics::ParseFailure, differential};
 
     const INPUT: &str = "2333133121414131402";
 
     #[test]
     fn test_part1() {
         let parsed = Day09::parser(&mut INPUT).unwrap();
-        assert_eq!(Day09::part_1(&parsed), 1928);
+        assert_eq!(Day09::part_1(&parsed, &()).unwrap(), 1928);
     }
 
     #[test]
     fn test_part2() {
         let parsed = Day09::parser(&mut INPUT).unwrap();
-        assert_eq!(Day09::part_2(&parsed), 2858);
+        assert_eq!(Day09::part_2(&parsed, &()).unwrap(), 2858);
+    }
+
+    #[test]
+    fn test_crlf() {
+        let error = Day09::parse_input("2333133121414131402\r\n", &[]).unwrap_err();
+        let failure = error.downcast_ref::<ParseFailure>().unwrap();
+        assert_eq!((failure.line, failure.column), (1, 20));
+        assert_eq!(failure.snippet, "2333133121414131402\\r");
+    }
+
+    #[test]
+    fn test_single_file() {
+        // the head used to look for an empty space after the last file
+        let parsed = Day09::parser(&mut "1").unwrap();
+        assert_eq!(Day09::part_1(&parsed, &()).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_head_past_tail() {
+        // the head used to move on to a file which had already been moved entirely
+        let parsed = Day09::parser(&mut "424692153680706").unwrap();
+        assert_eq!(Day09::part_1(&parsed, &()).unwrap(), 3736);
+    }
+
+    #[test]
+    fn test_reference() {
+        let parsed = Day09::parser(&mut INPUT).unwrap();
+        assert_eq!(Day09::reference_part_1(&parsed).unwrap().unwrap(), 1928);
+        assert_eq!(Day09::reference_part_2(&parsed).unwrap().unwrap(), 2858);
+        differential::check::<Day09>(differential::generated::<Day09>(200, 1..=50)).unwrap();
+        differential::check::<Day09>(differential::mutated(INPUT, 0, 500)).unwrap();
     }
 }
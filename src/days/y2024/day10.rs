@@ -1,19 +1,19 @@
 use std::collections::VecDeque;
 
+use anyhow::Result;
 use itertools::Itertools as _;
 use winnow::{
     ascii::line_ending,
     combinator::{repeat, separated},
+    error::StrContext,
     token::one_of,
     PResult, Parser as _,
 };
 
 use crate::days::Day;
 
-const GRID_SIZE: usize = if cfg!(test) { 8 } else { 48 };
-
 /// Top - Right - Bottom - Left
-const DIRS: [(i8, i8); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const DIRS: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
 
 pub struct Day10;
 
@@ -21,8 +21,8 @@ pub type HashSet<T> = std::collections::HashSet<T, ahash::RandomState>;
 
 #[derive(Debug, PartialEq, Eq, Default, Clone, Hash)]
 pub struct Point {
-    x: i8,
-    y: i8,
+    x: isize,
+    y: isize,
 }
 
 impl Point {
@@ -60,6 +60,7 @@ fn parse_row(input: &mut &str) -> PResult<Vec<u8>> {
         1..,
         one_of('0'..='9').map(|c: char| c.to_digit(10).unwrap() as u8),
     )
+    .context(StrContext::Label("row"))
     .parse_next(input)
 }
 
@@ -71,7 +72,7 @@ fn bfs_reach(start: &Point, map: &[Vec<u8>]) -> HashSet<Point> {
         if candidate.elevation(map) == 9 {
             goals.insert(candidate);
         } else {
-            to_visit.extend(candidate.neighbors(map).into_iter());
+            to_visit.extend(candidate.neighbors(map));
         }
     }
     goals
@@ -85,25 +86,36 @@ fn dfs_reach(start: &Point, map: &[Vec<u8>]) -> Vec<Point> {
         if candidate.elevation(map) == 9 {
             trails.push(candidate);
         } else {
-            to_visit.extend(candidate.neighbors(map).into_iter());
+            to_visit.extend(candidate.neighbors(map));
         }
     }
     trails
 }
 
 impl Day for Day10 {
+    const DAY: u32 = 10;
+
+    const TITLE: &'static str = "Hoof It";
+
+    const TAGS: &'static [&'static str] = &["grid", "bfs", "dfs"];
+
     type Input = Puzzle;
 
+    type Params = ();
+
     /// Parse the input elevation map and identify trail heads
     fn parser(input: &mut &str) -> PResult<Self::Input> {
-        let elevations: Vec<_> = separated(1.., parse_row, line_ending).parse_next(input)?;
+        let elevations: Vec<Vec<_>> = separated(1.., parse_row, line_ending)
+            .context(StrContext::Label("topographic map"))
+            .parse_next(input)?;
         let trail_heads: Vec<_> = elevations
             .iter()
-            .flatten()
-            .positions(|e| *e == 0)
-            .map(|idx| Point {
-                x: (idx % GRID_SIZE) as i8,
-                y: (idx / GRID_SIZE) as i8,
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.iter().positions(|e| *e == 0).map(move |x| Point {
+                    x: x as isize,
+                    y: y as isize,
+                })
             })
             .collect();
         Ok(Puzzle {
@@ -112,26 +124,28 @@ impl Day for Day10 {
         })
     }
 
+    type Prepared = ();
+
     type Output1 = usize;
 
     /// Part 1 took 335us
-    fn part_1(input: &Self::Input) -> Self::Output1 {
-        input
+    fn part_1(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output1> {
+        Ok(input
             .trail_heads
             .iter()
             .map(|p| bfs_reach(p, &input.map).len())
-            .sum()
+            .sum())
     }
 
     type Output2 = usize;
 
     /// Part 2 took 325.5us
-    fn part_2(input: &Self::Input) -> Self::Output2 {
-        input
+    fn part_2(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output2> {
+        Ok(input
             .trail_heads
             .iter()
             .map(|p| dfs_reach(p, &input.map).len())
-            .sum()
+            .sum())
     }
 }
 
@@ -152,6 +166,14 @@ mod tests {
     #[test]
     fn test_part2() {
         let parsed = Day10::parser(&mut INPUT).unwrap();
-        assert_eq!(Day10::part_2(&parsed), 81);
+        assert_eq!(Day10::part_2(&parsed, &()).unwrap(), 81);
+    }
+
+    #[test]
+    fn test_wide_map() {
+        let input = format!("{}0123456789", "5".repeat(200));
+        let parsed = Day10::parser(&mut input.as_str()).unwrap();
+        assert_eq!(parsed.trail_heads, [Point { x: 200, y: 0 }]);
+        assert_eq!(Day10::part_2(&parsed, &()).unwrap(), 1);
     }
 }
@@ -0,0 +1,147 @@
+use anyhow::Result;
+use itertools::Itertools as _;
+use winnow::{ascii::digit1, combinator::separated, error::StrContext, PResult, Parser as _};
+
+use crate::days::Day;
+
+pub struct Day11;
+
+pub type HashMap<K, T> = std::collections::HashMap<K, T, ahash::RandomState>;
+
+/// Recursively find out the expanded length of a stone given its number and the number of iterations
+fn expanded_length(
+    number: u64,
+    remaining: usize,
+    cache: &mut HashMap<(u64, usize), usize>,
+) -> usize {
+    // end condition, we reached the number of iterations and so we add one stone to the final count
+    if remaining == 0 {
+        return 1;
+    }
+    // check cache to see if we've already expanded a similar stone by the required amount of iterations
+    if let Some(len) = cache.get(&(number, remaining)) {
+        return *len;
+    }
+
+    // pre-calculate the number of digits
+    let digits = if number > 0 {
+        Some(number.ilog10() + 1)
+    } else {
+        None
+    };
+    // handle the rules
+    let res = match (number, digits) {
+        (0, _) => expanded_length(1, remaining - 1, cache),
+        (_, Some(d)) if d % 2 == 0 => {
+            let power = 10u64.pow(digits.unwrap() / 2);
+            let first = number / power;
+            let second = number - first * power;
+            expanded_length(first, remaining - 1, cache)
+                + expanded_length(second, remaining - 1, cache)
+        }
+        _ => expanded_length(number * 2024, remaining - 1, cache),
+    };
+    // populate the cache
+    cache.insert((number, remaining), res);
+    res
+}
+
+/// Blink once, changing each stone according to the rules
+fn blink(stones: &[u64]) -> Vec<u64> {
+    let mut next = Vec::with_capacity(stones.len() * 2);
+    for stone in stones {
+        let digits = stone.to_string();
+        if *stone == 0 {
+            next.push(1);
+        } else if digits.len() % 2 == 0 {
+            let (left, right) = digits.split_at(digits.len() / 2);
+            next.push(left.parse().unwrap());
+            next.push(right.parse().unwrap());
+        } else {
+            next.push(stone * 2024);
+        }
+    }
+    next
+}
+
+impl Day for Day11 {
+    const DAY: u32 = 11;
+
+    const TITLE: &'static str = "Plutonian Pebbles";
+
+    const TAGS: &'static [&'static str] = &["memoization", "recursion"];
+
+    type Input = Vec<u64>;
+
+    type Params = ();
+
+    fn parser(input: &mut &str) -> PResult<Self::Input> {
+        separated(1.., digit1.parse_to::<u64>(), ' ')
+            .context(StrContext::Label("stones"))
+            .parse_next(input)
+    }
+
+    type Prepared = ();
+
+    type Output1 = usize;
+
+    fn part_1(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output1> {
+        let mut cache = HashMap::default();
+        Ok(input
+            .iter()
+            .map(|v| expanded_length(*v, 25, &mut cache))
+            .sum())
+    }
+
+    type Output2 = usize;
+
+    fn part_2(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output2> {
+        let mut cache = HashMap::default();
+        Ok(input
+            .iter()
+            .map(|v| expanded_length(*v, 75, &mut cache))
+            .sum())
+    }
+
+    /// Actually blink 25 times (part 2 would need way too much memory)
+    fn reference_part_1(input: &Self::Input) -> Option<Result<Self::Output1>> {
+        let stones = (0..25).fold(input.clone(), |stones, _| blink(&stones));
+        Some(Ok(stones.len()))
+    }
+
+    /// Generate `size` stones with numbers of up to 6 digits
+    fn generate(seed: u64, size: usize) -> Option<String> {
+        let mut rng = fastrand::Rng::with_seed(seed);
+        Some(
+            (0..size.max(1))
+                .map(|_| {
+                    let digits = rng.u32(1..=6);
+                    rng.u64(0..10u64.pow(digits)).to_string()
+                })
+                .join(" "),
+        )
+    }
+}
+
+#[cfg(test)]
+#[allow(const_item_mutation)]
+mod tests {
+    use super::*;
+    use crate::differential;
+
+    const INPUT: &str = "125 17";
+
+    #[test]
+    fn test_part1() {
+        let parsed = Day11::parser(&mut INPUT).unwrap();
+        assert_eq!(Day11::part_1(&parsed, &()).unwrap(), 55312);
+    }
+
+    #[test]
+    fn test_reference() {
+        let parsed = Day11::parser(&mut INPUT).unwrap();
+        assert_eq!(Day11::reference_part_1(&parsed).unwrap().unwrap(), 55312);
+        differential::check::<Day11>(differential::generated::<Day11>(20, 1..=3)).unwrap();
+        differential::check::<Day11>(differential::mutated(INPUT, 0, 50)).unwrap();
+    }
+}
@@ -1,14 +1,15 @@
+use anyhow::Result;
 use itertools::Itertools as _;
 use winnow::{
     ascii::line_ending,
     combinator::{repeat, separated},
+    error::StrContext,
     token::one_of,
     PResult, Parser as _,
 };
 
 use crate::days::Day;
 
-const GRID_SIZE: usize = if cfg!(test) { 4 } else { 140 };
 // up - right - down - left
 const DIRS: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
 // top-right - bottom-right - bottom-left - top-left
@@ -110,7 +111,7 @@ impl Region {
 /// Use a BFS flooding algorithm to find all the plots belonging to the same region as `start`
 ///
 /// At the same time, calculate the number of corners in the region, as well as the perimeter length.
-fn bfs_flood(start: &Point, map: &[Vec<char>], part2: bool) -> Region {
+fn bfs_flood(start: &Point, map: &[Vec<char>]) -> Region {
     let mut perimeter = 0;
     let mut corners = 0;
     let mut region = HashSet::default();
@@ -122,9 +123,7 @@ fn bfs_flood(start: &Point, map: &[Vec<char>], part2: bool) -> Region {
         stack.extend(neighbors.into_iter().filter(|p| !region.contains(p)));
         if region.insert(plot) {
             perimeter += perimeter_increase;
-            if part2 {
-                corners += plot.count_corners(map);
-            }
+            corners += plot.count_corners(map);
         }
     }
     Region {
@@ -135,10 +134,10 @@ fn bfs_flood(start: &Point, map: &[Vec<char>], part2: bool) -> Region {
 }
 
 /// Create a list of all regions
-fn get_regions(map: &[Vec<char>], part2: bool) -> Vec<Region> {
+fn get_regions(map: &[Vec<char>]) -> Vec<Region> {
     let mut regions = Vec::<Region>::new();
-    for y in 0..GRID_SIZE {
-        for x in 0..GRID_SIZE {
+    for (y, row) in map.iter().enumerate() {
+        for x in 0..row.len() {
             let point = Point {
                 x: x as isize,
                 y: y as isize,
@@ -146,7 +145,7 @@ fn get_regions(map: &[Vec<char>], part2: bool) -> Vec<Region> {
             if regions.iter().any(|r| r.contains(&point)) {
                 continue;
             }
-            regions.push(bfs_flood(&point, map, part2));
+            regions.push(bfs_flood(&point, map));
         }
     }
     regions
@@ -154,36 +153,47 @@ fn get_regions(map: &[Vec<char>], part2: bool) -> Vec<Region> {
 
 /// Parse a line of the input
 fn parse_line(input: &mut &str) -> PResult<Vec<char>> {
-    repeat(1.., one_of('A'..='Z')).parse_next(input)
+    repeat(1.., one_of('A'..='Z'))
+        .context(StrContext::Label("row"))
+        .parse_next(input)
 }
 
 impl Day for Day12 {
+    const DAY: u32 = 12;
+
+    const TITLE: &'static str = "Garden Groups";
+
+    const TAGS: &'static [&'static str] = &["grid", "flood-fill"];
+
     type Input = Vec<Vec<char>>;
 
+    type Params = ();
+
     fn parser(input: &mut &str) -> PResult<Self::Input> {
-        separated(1.., parse_line, line_ending).parse_next(input)
+        separated(1.., parse_line, line_ending)
+            .context(StrContext::Label("garden map"))
+            .parse_next(input)
+    }
+
+    /// The regions of the garden, with their perimeter and number of corners
+    type Prepared = Vec<Region>;
+
+    fn prepare(input: &Self::Input) -> Result<Self::Prepared> {
+        Ok(get_regions(input))
     }
 
     type Output1 = usize;
 
     /// Part 1 took 15.3ms
-    fn part_1(input: &Self::Input) -> Self::Output1 {
-        let regions = get_regions(input, false);
-        regions
-            .into_iter()
-            .map(|r| r.points.len() * r.perimeter)
-            .sum()
+    fn part_1(_input: &Self::Input, regions: &Self::Prepared) -> Result<Self::Output1> {
+        Ok(regions.iter().map(|r| r.points.len() * r.perimeter).sum())
     }
 
     type Output2 = usize;
 
     /// Part 2 took 16.7ms
-    fn part_2(input: &Self::Input) -> Self::Output2 {
-        let regions = get_regions(input, true);
-        regions
-            .into_iter()
-            .map(|r| r.points.len() * r.corners)
-            .sum()
+    fn part_2(_input: &Self::Input, regions: &Self::Prepared) -> Result<Self::Output2> {
+        Ok(regions.iter().map(|r| r.points.len() * r.corners).sum())
     }
 }
 
@@ -200,12 +210,14 @@ EEEC";
     #[test]
     fn test_part1() {
         let parsed = Day12::parser(&mut INPUT).unwrap();
-        assert_eq!(Day12::part_1(&parsed), 140);
+        let regions = Day12::prepare(&parsed).unwrap();
+        assert_eq!(Day12::part_1(&parsed, &regions).unwrap(), 140);
     }
 
     #[test]
     fn test_part2() {
         let parsed = Day12::parser(&mut INPUT).unwrap();
-        assert_eq!(Day12::part_2(&parsed), 80);
+        let regions = Day12::prepare(&parsed).unwrap();
+        assert_eq!(Day12::part_2(&parsed, &regions).unwrap(), 80);
     }
 }
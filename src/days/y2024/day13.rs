@@ -0,0 +1,266 @@
+use anyhow::{anyhow, Result};
+use itertools::Itertools as _;
+use winnow::{
+    ascii::{digit1, line_ending},
+    combinator::{alt, separated, terminated},
+    error::StrContext,
+    seq, PResult, Parser as _,
+};
+
+use crate::days::Day;
+
+pub struct Day13;
+
+#[derive(Debug, Clone)]
+pub struct Offset {
+    x: isize,
+    y: isize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Claw {
+    a: Offset,
+    b: Offset,
+    prize: Offset,
+}
+
+impl Claw {
+    /// Calculate the minimum amount of tokens necessary to win the prize, pressing each button at most `max_presses`
+    /// times if given
+    ///
+    /// For each claw, we have a system of equations:
+    /// Px = a * Ax + b Bx; Py = a * Ay + b * By;
+    /// By solving it (with Cramer's rule), we can extract values of `a` (presses of A) and `b` (pressed of B) which
+    /// reach the prize location.
+    /// If those are integers, then we can reach the prize, otherwise we can't. In practice, we check this by putting
+    /// the values back into the two equations and checking the equality.
+    /// If the buttons move the claw in the same direction, the system has no unique solution. This never happens in the
+    /// real input, so we consider that the prize can't be won.
+    fn tokens(&self, max_presses: Option<isize>) -> Option<usize> {
+        let det = self.a.x * self.b.y - self.a.y * self.b.x;
+        if det == 0 {
+            return None;
+        }
+        let a = (self.prize.x * self.b.y - self.prize.y * self.b.x) / det;
+        let b = (self.a.x * self.prize.y - self.a.y * self.prize.x) / det;
+        let presses = 0..=max_presses.unwrap_or(isize::MAX);
+        if presses.contains(&a)
+            && presses.contains(&b)
+            && a * self.a.x + b * self.b.x == self.prize.x
+            && a * self.a.y + b * self.b.y == self.prize.y
+        {
+            Some(a as usize * 3 + b as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Try all combinations of up to 100 presses of each button, and find the cheapest one which reaches the prize
+    fn tokens_brute_force(&self) -> Option<usize> {
+        (0..=100)
+            .cartesian_product(0..=100)
+            .filter(|(a, b)| {
+                a * self.a.x + b * self.b.x == self.prize.x
+                    && a * self.a.y + b * self.b.y == self.prize.y
+            })
+            .map(|(a, b)| a as usize * 3 + b as usize)
+            .min()
+    }
+
+    /// For part 2, we need to add a constant to the prize position
+    fn part2(&self) -> Self {
+        let mut new_claw = self.clone();
+        new_claw.prize.x += 10000000000000;
+        new_claw.prize.y += 10000000000000;
+        new_claw
+    }
+}
+
+/// Parse a button definition into an [`Offset`]
+fn parse_button(input: &mut &str) -> PResult<Offset> {
+    terminated(
+        seq!(Offset {
+            _: alt(("Button A: X+", "Button B: X+")),
+            x: digit1.parse_to::<isize>(),
+            _: ", Y+",
+            y: digit1.parse_to::<isize>()
+        }),
+        line_ending,
+    )
+    .context(StrContext::Label("button"))
+    .parse_next(input)
+}
+
+/// Parse a prize location definition into an [`Offset`]
+fn parse_prize(input: &mut &str) -> PResult<Offset> {
+    seq!(Offset {
+        _: "Prize: X=",
+        x: digit1.parse_to::<isize>(),
+        _: ", Y=",
+        y: digit1.parse_to::<isize>()
+    })
+    .context(StrContext::Label("prize"))
+    .parse_next(input)
+}
+
+/// Generate `size` claw machines
+///
+/// The buttons move the claw by 10 to 99 in each direction. Half of the prizes can be reached with up to 120 presses of
+/// each button, the others are placed randomly.
+fn generate_claws(rng: &mut fastrand::Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let (ax, ay, bx, by) = (
+                rng.isize(10..100),
+                rng.isize(10..100),
+                rng.isize(10..100),
+                rng.isize(10..100),
+            );
+            let (px, py) = if rng.bool() {
+                let (a, b) = (rng.isize(0..=120), rng.isize(0..=120));
+                (a * ax + b * bx, a * ay + b * by)
+            } else {
+                (rng.isize(0..20000), rng.isize(0..20000))
+            };
+            format!("Button A: X+{ax}, Y+{ay}\nButton B: X+{bx}, Y+{by}\nPrize: X={px}, Y={py}")
+        })
+        .join("\n\n")
+}
+
+impl Day for Day13 {
+    const DAY: u32 = 13;
+
+    const TITLE: &'static str = "Claw Contraption";
+
+    const TAGS: &'static [&'static str] = &["math", "linear-equations"];
+
+    type Input = Vec<Claw>;
+
+    type Params = ();
+
+    /// Parse the list of claw machines into a list
+    fn parser(input: &mut &str) -> PResult<Self::Input> {
+        separated(
+            1..,
+            seq!(Claw {
+                a: parse_button,
+                b: parse_button,
+                prize: parse_prize,
+            }),
+            (line_ending, line_ending),
+        )
+        .context(StrContext::Label("claw machines"))
+        .parse_next(input)
+    }
+
+    type Prepared = ();
+
+    type Output1 = usize;
+
+    /// Part 1 took 4.53us
+    fn part_1(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output1> {
+        Ok(input
+            .iter()
+            .map(|c| c.tokens(Some(100)).unwrap_or_default())
+            .sum())
+    }
+
+    type Output2 = usize;
+
+    /// Part 2 took 4.37us
+    fn part_2(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output2> {
+        Ok(input
+            .iter()
+            .map(|c| c.part2().tokens(None).unwrap_or_default())
+            .sum())
+    }
+
+    /// Try all combinations of presses
+    ///
+    /// Machines whose buttons move the claw in the same direction are rejected, the real inputs don't have any.
+    fn reference_part_1(input: &Self::Input) -> Option<Result<Self::Output1>> {
+        if input.iter().any(|c| c.a.x * c.b.y == c.a.y * c.b.x) {
+            return Some(Err(anyhow!("the buttons of a claw machine are collinear")));
+        }
+        Some(Ok(input
+            .iter()
+            .map(|c| c.tokens_brute_force().unwrap_or_default())
+            .sum()))
+    }
+
+    /// Generate `size` claw machines
+    fn generate(seed: u64, size: usize) -> Option<String> {
+        Some(generate_claws(&mut fastrand::Rng::with_seed(seed), size))
+    }
+}
+
+#[cfg(test)]
+#[allow(const_item_mutation)]
+mod tests {
+    use super::*;
+    use crate::differential;
+
+    const INPUT: &str = "Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279";
+
+    #[test]
+    fn test_part1() {
+        let parsed = Day13::parser(&mut INPUT).unwrap();
+        assert_eq!(Day13::part_1(&parsed, &()).unwrap(), 480);
+    }
+
+    #[test]
+    fn test_no_division_by_zero() {
+        // button A doesn't move along X, and the buttons of the second machine are collinear
+        let mut input = "Button A: X+0, Y+1
+Button B: X+52, Y+13
+Prize: X=3380, Y=846
+
+Button A: X+10, Y+20
+Button B: X+20, Y+40
+Prize: X=100, Y=200";
+        let parsed = Day13::parser(&mut input).unwrap();
+        assert_eq!(Day13::part_1(&parsed, &()).unwrap(), 68);
+    }
+
+    #[test]
+    fn test_zero_presses() {
+        // the prize is reached with button B alone
+        let mut input = "Button A: X+0, Y+1
+Button B: X+52, Y+13
+Prize: X=3380, Y=845";
+        let parsed = Day13::parser(&mut input).unwrap();
+        assert_eq!(Day13::part_1(&parsed, &()).unwrap(), 65);
+    }
+
+    #[test]
+    fn test_press_limit() {
+        // the prize needs 103 presses of button A, more than the 100 allowed in part 1
+        let mut input = "Button A: X+25, Y+15
+Button B: X+23, Y+49
+Prize: X=2667, Y=1741";
+        let parsed = Day13::parser(&mut input).unwrap();
+        assert_eq!(Day13::part_1(&parsed, &()).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_reference() {
+        let parsed = Day13::parser(&mut INPUT).unwrap();
+        assert_eq!(Day13::reference_part_1(&parsed).unwrap().unwrap(), 480);
+        differential::check::<Day13>(differential::generated::<Day13>(100, 1..=10)).unwrap();
+        differential::check::<Day13>(differential::mutated(INPUT, 0, 200)).unwrap();
+    }
+}
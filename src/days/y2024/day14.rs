@@ -0,0 +1,266 @@
+use std::cmp::Ordering;
+
+use anyhow::{bail, Result};
+use itertools::Itertools as _;
+use winnow::{
+    ascii::{dec_int, line_ending},
+    combinator::separated,
+    error::StrContext,
+    seq,
+    token::one_of,
+    PResult, Parser as _,
+};
+
+use crate::days::{Day, Params};
+
+pub struct Day14;
+
+/// The dimensions of the area where the robots move, which are not part of the input
+///
+/// The area of the example is 11 tiles wide and 7 tiles tall.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Area {
+    width: isize,
+    height: isize,
+}
+
+impl Default for Area {
+    fn default() -> Self {
+        Self {
+            width: 101,
+            height: 103,
+        }
+    }
+}
+
+impl Params for Area {
+    const NAMES: &'static [&'static str] = &["width", "height"];
+
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        let value = value.parse()?;
+        if value <= 0 {
+            bail!("the {name} of the area must be positive");
+        }
+        match name {
+            "width" => self.width = value,
+            "height" => self.height = value,
+            _ => bail!("unknown parameter {name}"),
+        }
+        Ok(())
+    }
+}
+
+/// A quadrant, or no quadrant (in the middle of the grid)
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Quadrant {
+    None,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// An offset into the grid, either a position or a velocity vector
+#[derive(Debug, Clone)]
+pub struct Offset {
+    x: isize,
+    y: isize,
+}
+
+impl Offset {
+    /// Knowing a position on the grid, return which quadrant it belongs to
+    ///
+    /// Positions that lie on the center lines are in [`Quandrant::None`].
+    fn quadrant(&self, area: Area) -> Quadrant {
+        match (
+            self.x.cmp(&(area.width / 2)),
+            self.y.cmp(&(area.height / 2)),
+        ) {
+            (Ordering::Equal, _) | (_, Ordering::Equal) => Quadrant::None,
+            (Ordering::Less, Ordering::Less) => Quadrant::TopLeft,
+            (_, Ordering::Less) => Quadrant::TopRight,
+            (Ordering::Less, _) => Quadrant::BottomLeft,
+            _ => Quadrant::BottomRight,
+        }
+    }
+}
+
+/// A robot with its initial position and velocity
+#[derive(Debug, Clone)]
+pub struct Robot {
+    start: Offset,
+    vel: Offset,
+}
+
+impl Robot {
+    /// Calculate the position of a robot after `time` seconds elapsed
+    fn pos_after(&self, time: isize, area: Area) -> Offset {
+        let x = self.start.x + time * self.vel.x;
+        let y = self.start.y + time * self.vel.y;
+        Offset {
+            x: x.rem_euclid(area.width),
+            y: y.rem_euclid(area.height),
+        }
+    }
+}
+
+/// The robots, and the area where they move
+#[derive(Debug, Clone)]
+pub struct Puzzle {
+    robots: Vec<Robot>,
+    area: Area,
+}
+
+/// Parse an offset value (either position or velocity)
+fn parse_offset(input: &mut &str) -> PResult<Offset> {
+    seq!(Offset {
+        _: one_of(('v', 'p')),
+        _: '=',
+        x: dec_int,
+        _: ',',
+        y: dec_int,
+    })
+    .context(StrContext::Label("offset"))
+    .parse_next(input)
+}
+
+/// Parse a robot entry
+fn parse_robot(input: &mut &str) -> PResult<Robot> {
+    seq!(Robot{
+        start: parse_offset,
+        _: ' ',
+        vel: parse_offset,
+    })
+    .context(StrContext::Label("robot"))
+    .parse_next(input)
+}
+
+/// Print the position of the robots on the grid at a given time
+#[allow(unused)]
+fn print_robots_at_time(robots: &[Robot], time: isize, area: Area) {
+    let mut grid = vec![vec!['.'; area.width as usize]; area.height as usize];
+    for robot in robots {
+        let pos = robot.pos_after(time, area);
+        grid[pos.y as usize][pos.x as usize] = '#';
+    }
+    for y in 0..area.height {
+        for x in 0..area.width {
+            print!("{}", grid[y as usize][x as usize]);
+        }
+        println!();
+    }
+}
+
+/// Find the variance of the x and y coordinates of the robots at a given time.
+///
+/// We suppose that a shape must be comprised of a bunch of robots in close proximity, which would give a low variance.
+///
+/// In reality I did this by printing a lot of grids where a bunch of robots has the same X or Y coordinate
+/// and looking at the output visually.
+fn robots_location_variance(robots: &[Robot], time: isize, area: Area) -> (usize, usize) {
+    let positions = robots.iter().map(|r| r.pos_after(time, area)).collect_vec();
+    let (mean_x, mean_y) = positions
+        .iter()
+        .fold((0, 0), |acc, pos| (acc.0 + pos.x, acc.1 + pos.y));
+    let (sum_diff_x, sum_diff_y) = positions.iter().fold((0, 0), |acc, pos| {
+        (
+            acc.0 + (pos.x - mean_x).pow(2),
+            acc.1 + (pos.y - mean_y).pow(2),
+        )
+    });
+    (
+        sum_diff_x as usize / robots.len(),
+        sum_diff_y as usize / robots.len(),
+    )
+}
+
+impl Day for Day14 {
+    const DAY: u32 = 14;
+
+    const TITLE: &'static str = "Restroom Redoubt";
+
+    const TAGS: &'static [&'static str] = &["simulation", "statistics"];
+
+    type Input = Puzzle;
+
+    type Params = Area;
+
+    fn params(input: &mut Self::Input) -> Option<&mut Self::Params> {
+        Some(&mut input.area)
+    }
+
+    /// Parse the robots, which move in the area of the real puzzle
+    fn parser(input: &mut &str) -> PResult<Self::Input> {
+        let robots = separated(1.., parse_robot, line_ending)
+            .context(StrContext::Label("robots"))
+            .parse_next(input)?;
+        Ok(Puzzle {
+            robots,
+            area: Area::default(),
+        })
+    }
+
+    type Prepared = ();
+
+    type Output1 = usize;
+
+    /// Part 1 took 12.03us
+    fn part_1(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output1> {
+        Ok(input
+            .robots
+            .iter()
+            .map(|r| r.pos_after(100, input.area))
+            .counts_by(|pos| pos.quadrant(input.area))
+            .into_iter()
+            .filter_map(|(q, c)| match q {
+                Quadrant::None => None,
+                _ => Some(c),
+            })
+            .product())
+    }
+
+    type Output2 = usize;
+
+    /// Part 2 took 14.3ms
+    fn part_2(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output2> {
+        // find a time where the variance of x multiplied by the variance of y is minimal
+        let (time, _) = (0..10000)
+            .map(|time| {
+                let variance = robots_location_variance(&input.robots, time, input.area);
+                (time, variance)
+            })
+            .min_by_key(|(_, (x, y))| *x * *y)
+            .unwrap();
+        // print_robots_at_time(&input.robots, time, input.area);
+        Ok(time as usize)
+    }
+}
+
+#[cfg(test)]
+#[allow(const_item_mutation)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3";
+
+    #[test]
+    fn test_part1() {
+        let mut parsed = Day14::parser(&mut INPUT).unwrap();
+        parsed.area = Area {
+            width: 11,
+            height: 7,
+        };
+        assert_eq!(Day14::part_1(&parsed, &()).unwrap(), 12);
+    }
+}
@@ -1,16 +1,17 @@
 use std::ops::{Deref, DerefMut};
 
+use anyhow::Result;
+use itertools::Itertools as _;
 use winnow::{
     ascii::line_ending,
     combinator::{opt, repeat, separated, separated_pair},
+    error::{StrContext, StrContextValue},
     token::one_of,
     PResult, Parser as _,
 };
 
 use crate::days::Day;
 
-const GRID_SIZE: usize = if cfg!(test) { 10 } else { 50 };
-
 pub type HashSet<T> = std::collections::HashSet<T, ahash::RandomState>;
 
 pub struct Day15;
@@ -43,12 +44,25 @@ fn parse_row(input: &mut &str) -> PResult<Vec<Tile>> {
             _ => unimplemented!(),
         }),
     )
+    .context(StrContext::Label("row"))
     .parse_next(input)
 }
 
-fn parse_grid(input: &mut &str) -> PResult<Grid> {
-    let tiles: Vec<Vec<_>> = separated(1.., parse_row, line_ending).parse_next(input)?;
-    Ok(Grid(tiles))
+/// Parse the warehouse, and replace the robot with the floor under it
+fn parse_grid(input: &mut &str) -> PResult<(Grid, Loc)> {
+    separated(1.., parse_row, line_ending)
+        .verify_map(|tiles| {
+            let mut grid = Grid(tiles);
+            // extract robot position data
+            let start_pos = grid.start_pos()?;
+            grid[start_pos.y][start_pos.x] = Tile::Floor;
+            Some((grid, start_pos))
+        })
+        .context(StrContext::Label("warehouse"))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "a robot (`@`)",
+        )))
+        .parse_next(input)
 }
 
 fn parse_move(input: &mut &str) -> PResult<Move> {
@@ -60,11 +74,14 @@ fn parse_move(input: &mut &str) -> PResult<Move> {
             '<' => Move::Left,
             _ => unimplemented!(),
         })
+        .context(StrContext::Label("move"))
         .parse_next(input)
 }
 
 fn parse_moves(input: &mut &str) -> PResult<Vec<Move>> {
-    let res: Vec<_> = repeat(1.., (parse_move, opt(line_ending))).parse_next(input)?;
+    let res: Vec<_> = repeat(1.., (parse_move, opt(line_ending)))
+        .context(StrContext::Label("moves"))
+        .parse_next(input)?;
     let (moves, _): (Vec<_>, Vec<_>) = res.into_iter().unzip();
     Ok(moves)
 }
@@ -119,22 +136,24 @@ impl DerefMut for Grid {
 }
 
 impl Grid {
-    /// Find the start position of the robot
-    fn start_pos(&self) -> Loc {
-        self.iter()
-            .flatten()
-            .enumerate()
-            .find_map(|(i, tile)| {
-                if *tile == Tile::Robot {
-                    Some(Loc {
-                        x: i % GRID_SIZE,
-                        y: i / GRID_SIZE,
-                    })
-                } else {
-                    None
-                }
-            })
-            .unwrap()
+    /// The number of tiles in a row of the grid
+    fn width(&self) -> usize {
+        self.first().map(Vec::len).unwrap_or_default()
+    }
+
+    /// Find the start position of the robot, if there is one
+    fn start_pos(&self) -> Option<Loc> {
+        let width = self.width();
+        self.iter().flatten().enumerate().find_map(|(i, tile)| {
+            if *tile == Tile::Robot {
+                Some(Loc {
+                    x: i % width,
+                    y: i / width,
+                })
+            } else {
+                None
+            }
+        })
     }
 
     /// Get the tile type at coordinates `x` and `y`
@@ -148,23 +167,14 @@ impl Grid {
     }
 
     /// Calculate the GPS score
-    fn gps_score(&self, part2: bool) -> usize {
+    fn gps_score(&self) -> usize {
+        let width = self.width();
         self.iter()
             .flatten()
             .enumerate()
             .filter_map(|(i, tile)| {
                 if *tile == Tile::BoxLeft {
-                    let x = if part2 {
-                        i % (2 * GRID_SIZE)
-                    } else {
-                        i % GRID_SIZE
-                    };
-                    let y = if part2 {
-                        i / (2 * GRID_SIZE)
-                    } else {
-                        i / GRID_SIZE
-                    };
-                    Some(y * 100 + x)
+                    Some((i / width) * 100 + i % width)
                 } else {
                     None
                 }
@@ -197,8 +207,8 @@ impl Puzzle {
                 // the neighbor is a box, let's see if there's an empty tile after all the in-line boxes
                 let iter: Box<dyn Iterator<Item = usize>> = match mov {
                     Move::Up => Box::new((0..self.robot.y).rev()),
-                    Move::Right => Box::new((self.robot.x + 1)..GRID_SIZE),
-                    Move::Down => Box::new((self.robot.y + 1)..GRID_SIZE),
+                    Move::Right => Box::new((self.robot.x + 1)..self.grid.width()),
+                    Move::Down => Box::new((self.robot.y + 1)..self.grid.len()),
                     Move::Left => Box::new((0..self.robot.x).rev()),
                 };
                 let next = match mov {
@@ -404,9 +414,8 @@ impl Puzzle {
     /// Print the grid with the current state
     #[allow(unused)]
     fn print(&self, part2: bool) {
-        let width = if part2 { GRID_SIZE * 2 } else { GRID_SIZE };
-        for y in 0..GRID_SIZE {
-            for x in 0..width {
+        for y in 0..self.grid.len() {
+            for x in 0..self.grid.width() {
                 if self.robot.x == x && self.robot.y == y {
                     print!("@");
                     continue;
@@ -430,44 +439,86 @@ impl Puzzle {
     }
 }
 
+/// Generate a random square warehouse of `size` by `size` tiles (walls included) and a list of moves for the robot
+///
+/// The warehouse is surrounded by walls, about a fifth of the inner tiles contain a box and a few are walls. The robot
+/// makes 8 moves per tile, listed on lines of 1000 moves like in the real input.
+fn generate_warehouse(rng: &mut fastrand::Rng, size: usize) -> String {
+    let size = size.max(3);
+    let mut grid = vec![vec!['#'; size]; size];
+    for row in grid.iter_mut().take(size - 1).skip(1) {
+        for tile in row.iter_mut().take(size - 1).skip(1) {
+            *tile = match rng.u8(0..100) {
+                0..5 => '#',
+                5..25 => 'O',
+                _ => '.',
+            };
+        }
+    }
+    grid[rng.usize(1..size - 1)][rng.usize(1..size - 1)] = '@';
+    let moves: Vec<_> = (0..size * size * 8)
+        .map(|_| ['^', '>', 'v', '<'][rng.usize(0..4)])
+        .collect();
+    let grid = grid
+        .iter()
+        .map(|row| row.iter().collect::<String>())
+        .join("\n");
+    let moves = moves
+        .chunks(1000)
+        .map(|line| line.iter().collect::<String>())
+        .join("\n");
+    format!("{grid}\n\n{moves}")
+}
+
 impl Day for Day15 {
+    const DAY: u32 = 15;
+
+    const TITLE: &'static str = "Warehouse Woes";
+
+    const TAGS: &'static [&'static str] = &["grid", "simulation"];
+
     type Input = Puzzle;
 
+    type Params = ();
+
     fn parser(input: &mut &str) -> PResult<Self::Input> {
-        let (mut grid, moves) =
+        let ((grid, robot), moves) =
             separated_pair(parse_grid, "\n\n", parse_moves).parse_next(input)?;
-        // extract robot position data
-        let start_pos = grid.start_pos();
-        grid[start_pos.y][start_pos.x] = Tile::Floor;
-        Ok(Puzzle {
-            grid,
-            robot: start_pos,
-            moves,
-        })
+        Ok(Puzzle { grid, robot, moves })
     }
 
+    type Prepared = ();
+
     type Output1 = usize;
 
     /// Part 1 took 214us
-    fn part_1(input: &Self::Input) -> Self::Output1 {
+    fn part_1(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output1> {
         let mut data = input.clone();
         for mov in data.moves.clone() {
             data.move_robot(mov);
         }
         // data.print(false);
-        data.grid.gps_score(false)
+        Ok(data.grid.gps_score())
     }
 
     type Output2 = usize;
 
     /// Part 2 took 1.40ms
-    fn part_2(input: &Self::Input) -> Self::Output2 {
+    fn part_2(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output2> {
         let mut data = input.clone().expand();
         for mov in data.moves.clone() {
             data.move_robot_part2(mov);
         }
         // data.print(true);
-        data.grid.gps_score(true)
+        Ok(data.grid.gps_score())
+    }
+
+    /// Generate a warehouse of `size` by `size` tiles
+    fn generate(seed: u64, size: usize) -> Option<String> {
+        Some(generate_warehouse(
+            &mut fastrand::Rng::with_seed(seed),
+            size,
+        ))
     }
 }
 
@@ -475,6 +526,7 @@ impl Day for Day15 {
 #[allow(const_item_mutation)]
 mod tests {
     use super::*;
+    use crate::diagnostics::ParseFailure;
 
     const INPUT: &str = "##########
 #..O..O.O#
@@ -501,12 +553,32 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
     #[test]
     fn test_part1() {
         let parsed = Day15::parser(&mut INPUT).unwrap();
-        assert_eq!(Day15::part_1(&parsed), 10092);
+        assert_eq!(Day15::part_1(&parsed, &()).unwrap(), 10092);
     }
 
     #[test]
     fn test_part2() {
         let parsed = Day15::parser(&mut INPUT).unwrap();
-        assert_eq!(Day15::part_2(&parsed), 9021);
+        assert_eq!(Day15::part_2(&parsed, &()).unwrap(), 9021);
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = Day15::generate(42, 30).unwrap();
+        assert_eq!(Day15::generate(42, 30).unwrap(), input);
+        let parsed = Day15::parser(&mut input.as_str()).unwrap();
+        assert_eq!(parsed.grid.len(), 30);
+        assert_eq!(parsed.moves.len(), 30 * 30 * 8);
+        Day15::part_1(&parsed, &()).unwrap();
+        Day15::part_2(&parsed, &()).unwrap();
+    }
+
+    #[test]
+    fn test_no_robot() {
+        let error = Day15::parse_input("####\n#.O#\n####\n\n<>", &[]).unwrap_err();
+        let failure = error.downcast_ref::<ParseFailure>().unwrap();
+        assert_eq!((failure.line, failure.column), (1, 1));
+        assert_eq!(failure.labels, ["warehouse"]);
+        assert_eq!(failure.expected, ["a robot (`@`)"]);
     }
 }
@@ -1,3 +1,4 @@
+use anyhow::{Context as _, Result};
 use pathfinding::{
     directed::astar::{astar, astar_bag},
     grid::Grid,
@@ -5,14 +6,13 @@ use pathfinding::{
 use winnow::{
     ascii::line_ending,
     combinator::{repeat, separated},
+    error::StrContext,
     token::one_of,
     PResult, Parser as _,
 };
 
 use crate::days::Day;
 
-const GRID_SIZE: usize = if cfg!(test) { 15 } else { 141 };
-
 pub type HashSet<T> = std::collections::HashSet<T, ahash::RandomState>;
 
 pub struct Day16;
@@ -126,29 +126,42 @@ pub struct Puzzle {
 
 /// Parse a line of the maze
 fn parse_line(input: &mut &str) -> PResult<Vec<char>> {
-    repeat(1.., one_of(('#', '.', 'E', 'S'))).parse_next(input)
+    repeat(1.., one_of(('#', '.', 'E', 'S')))
+        .context(StrContext::Label("row"))
+        .parse_next(input)
 }
 
 /// Parse the maze into a list of list of characters
 fn parse_grid(input: &mut &str) -> PResult<Vec<Vec<char>>> {
-    separated(1.., parse_line, line_ending).parse_next(input)
+    separated(1.., parse_line, line_ending)
+        .context(StrContext::Label("maze"))
+        .parse_next(input)
 }
 
 impl Day for Day16 {
+    const DAY: u32 = 16;
+
+    const TITLE: &'static str = "Reindeer Maze";
+
+    const TAGS: &'static [&'static str] = &["grid", "pathfinding"];
+
     type Input = Puzzle;
 
+    type Params = ();
+
     /// Parse the input into a grid, collecting the coordinates of the start and end positions
     fn parser(input: &mut &str) -> PResult<Self::Input> {
         let mut start = Pos::default();
         let mut end = Pos::default();
-        let mut grid: Grid = parse_grid
-            .parse_next(input)?
+        let rows = parse_grid.parse_next(input)?;
+        let width = rows[0].len();
+        let mut grid: Grid = rows
             .into_iter()
             .flatten()
             .enumerate()
             .filter_map(|(i, c)| {
-                let x = i % GRID_SIZE;
-                let y = i / GRID_SIZE;
+                let x = i % width;
+                let y = i / width;
                 match c {
                     'S' => {
                         start = (x, y).into();
@@ -168,38 +181,40 @@ impl Day for Day16 {
         Ok(Puzzle { grid, start, end })
     }
 
+    type Prepared = ();
+
     type Output1 = usize;
 
     /// Part 1 took 7.48ms
     ///
     /// To see my implementation of A*, check out <https://github.com/beeb/aoc-2022/blob/main/src/days/day12.rs>
     /// Here I used a lib.
-    fn part_1(input: &Self::Input) -> Self::Output1 {
-        astar(
+    fn part_1(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output1> {
+        let (_, score) = astar(
             &input.start,
             |p| p.successors(&input.grid),
             |p| p.distance(&input.grid, &input.end),
             |p| p.x == input.end.x && p.y == input.end.y,
         )
-        .unwrap()
-        .1
+        .context("no path from the start to the end")?;
+        Ok(score)
     }
 
     type Output2 = usize;
 
     /// Part 2 took 13.15ms
-    fn part_2(input: &Self::Input) -> Self::Output2 {
-        astar_bag(
+    fn part_2(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output2> {
+        let (paths, _) = astar_bag(
             &input.start,
             |p| p.successors(&input.grid),
             |p| p.distance(&input.grid, &input.end),
             |p| p.x == input.end.x && p.y == input.end.y,
         )
-        .unwrap()
-        .0
-        .flat_map(|path| path.into_iter().map(|pos| (pos.x, pos.y)))
-        .collect::<HashSet<_>>()
-        .len()
+        .context("no path from the start to the end")?;
+        Ok(paths
+            .flat_map(|path| path.into_iter().map(|pos| (pos.x, pos.y)))
+            .collect::<HashSet<_>>()
+            .len())
     }
 }
 
@@ -227,6 +242,6 @@ mod tests {
     #[test]
     fn test_part1() {
         let parsed = Day16::parser(&mut INPUT).unwrap();
-        assert_eq!(Day16::part_1(&parsed), 7036);
+        assert_eq!(Day16::part_1(&parsed, &()).unwrap(), 7036);
     }
 }
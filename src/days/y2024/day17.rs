@@ -1,7 +1,9 @@
+use anyhow::{bail, Context as _, Result};
 use itertools::Itertools as _;
 use winnow::{
     ascii::{dec_uint, digit1, line_ending},
     combinator::{preceded, separated, separated_pair},
+    error::StrContext,
     token::one_of,
     PResult, Parser as _,
 };
@@ -32,6 +34,37 @@ pub enum Instruction {
     Cdv(ComboOp), // divide A by 2**op -> C
 }
 
+impl ComboOp {
+    /// Decode the operand of an instruction which takes a combo operand
+    fn decode(operand: u8) -> Result<Self> {
+        Ok(match operand {
+            0..=3 => ComboOp::Lit(operand),
+            4 => ComboOp::RegisterA,
+            5 => ComboOp::RegisterB,
+            6 => ComboOp::RegisterC,
+            _ => bail!("invalid combo operand {operand}"),
+        })
+    }
+}
+
+impl Instruction {
+    /// Decode an instruction from its opcode and operand
+    pub fn decode(opcode: u8, operand: u8) -> Result<Self> {
+        let combo = || ComboOp::decode(operand);
+        Ok(match opcode {
+            0 => Instruction::Adv(combo()?),
+            1 => Instruction::Bxl(operand),
+            2 => Instruction::Bst(combo()?),
+            3 => Instruction::Jnz(operand),
+            4 => Instruction::Bxc,
+            5 => Instruction::Out(combo()?),
+            6 => Instruction::Bdv(combo()?),
+            7 => Instruction::Cdv(combo()?),
+            _ => bail!("invalid opcode {opcode}"),
+        })
+    }
+}
+
 /// The state of the VM comprised of 3 registers and an instruction pointer
 #[derive(Debug, Clone)]
 pub struct State {
@@ -44,8 +77,40 @@ pub struct State {
 }
 
 impl State {
+    /// Create a VM with the initial values of the registers A, B and C, which will run the program given as bytecode
+    ///
+    /// Fails if the program contains an invalid instruction.
+    pub fn new(registers: (usize, usize, usize), program: Vec<u8>) -> Result<Self> {
+        let (opcodes, rest) = program.as_chunks::<2>();
+        if !rest.is_empty() {
+            bail!("the program should contain pairs of an opcode and an operand");
+        }
+        let instructions = opcodes
+            .iter()
+            .map(|[opcode, operand]| Instruction::decode(*opcode, *operand))
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            a: registers.0,
+            b: registers.1,
+            c: registers.2,
+            pointer: 0,
+            instructions,
+            orig: program,
+        })
+    }
+
+    /// The current values of the registers A, B and C
+    pub fn registers(&self) -> (usize, usize, usize) {
+        (self.a, self.b, self.c)
+    }
+
+    /// The bytecode of the program
+    pub fn program(&self) -> &[u8] {
+        &self.orig
+    }
+
     /// Clone the state while changing the initial value of the A register
-    fn with_register(&self, a: usize) -> Self {
+    pub fn with_register(&self, a: usize) -> Self {
         let clone = self.clone();
         Self { a, ..clone }
     }
@@ -145,73 +210,102 @@ fn parse_register(input: &mut &str) -> PResult<usize> {
         ": ",
         digit1.parse_to(),
     )
+        .context(StrContext::Label("register"))
         .parse_next(input)?;
     Ok(reg)
 }
 
 /// Parse the 3 registers' initial values
 fn parse_registers(input: &mut &str) -> PResult<(usize, usize, usize)> {
-    let registers: Vec<_> = separated(3, parse_register, line_ending).parse_next(input)?;
+    let registers: Vec<_> = separated(3, parse_register, line_ending)
+        .context(StrContext::Label("registers"))
+        .parse_next(input)?;
     Ok(registers.into_iter().collect_tuple().unwrap())
 }
 
 /// Parse the raw bytecode of the program
 fn parse_instructions(input: &mut &str) -> PResult<Vec<u8>> {
-    preceded("Program: ", separated(1.., dec_uint::<_, u8, _>, ',')).parse_next(input)
+    preceded("Program: ", separated(1.., dec_uint::<_, u8, _>, ','))
+        .context(StrContext::Label("program"))
+        .parse_next(input)
+}
+
+/// Generate a random program with the same structure as the real inputs, and an initial value for register A which makes
+/// it output `size` values
+///
+/// Like the real programs, it is a single loop which shifts register A by 3 bits on each iteration and outputs a value
+/// computed from its lowest bits. We only keep programs for which part 2 has a solution.
+fn generate_program(rng: &mut fastrand::Rng, size: usize) -> String {
+    // register A can hold up to 21 3-bit values
+    let size = size.clamp(1, 21);
+    let program = loop {
+        // operand 7 is reserved, so we don't use it even where it would be ignored
+        let mut program = vec![2, 4, 1, rng.u8(0..7), 7, 5];
+        let mut mix = [[1, rng.u8(0..7)], [4, rng.u8(0..7)]];
+        if rng.bool() {
+            mix.reverse();
+        }
+        program.extend(mix.as_flattened());
+        if rng.bool() {
+            program.extend([0, 3, 5, 5]);
+        } else {
+            program.extend([5, 5, 0, 3]);
+        }
+        program.extend([3, 0]);
+        let text = format!(
+            "Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: {}",
+            program.iter().join(",")
+        );
+        let state =
+            Day17::parser(&mut text.as_str()).expect("the generated program should be valid");
+        if find_input(&state, 0, 0).is_some() {
+            break program;
+        }
+    };
+    let a = rng.usize(1 << (3 * (size - 1))..1 << (3 * size));
+    format!(
+        "Register A: {a}\nRegister B: 0\nRegister C: 0\n\nProgram: {}",
+        program.iter().join(",")
+    )
 }
 
 impl Day for Day17 {
+    const DAY: u32 = 17;
+
+    const TITLE: &'static str = "Chronospatial Computer";
+
+    const TAGS: &'static [&'static str] = &["vm", "reverse-engineering", "recursion"];
+
     type Input = State;
 
+    type Params = ();
+
     /// Transform the raw bytecode into a nice typed definition of the program and state
     fn parser(input: &mut &str) -> PResult<Self::Input> {
-        let (registers, instructions) =
-            separated_pair(parse_registers, "\n\n", parse_instructions).parse_next(input)?;
-        let instructions_parsed = instructions
-            .chunks_exact(2)
-            .map(|instr| {
-                let op = match instr[1] {
-                    0..=3 => ComboOp::Lit(instr[1]),
-                    4 => ComboOp::RegisterA,
-                    5 => ComboOp::RegisterB,
-                    6 => ComboOp::RegisterC,
-                    _ => unimplemented!(),
-                };
-                match instr[0] {
-                    0 => Instruction::Adv(op),
-                    1 => Instruction::Bxl(instr[1]),
-                    2 => Instruction::Bst(op),
-                    3 => Instruction::Jnz(instr[1]),
-                    4 => Instruction::Bxc,
-                    5 => Instruction::Out(op),
-                    6 => Instruction::Bdv(op),
-                    7 => Instruction::Cdv(op),
-                    _ => unimplemented!(),
-                }
-            })
-            .collect();
-        Ok(State {
-            a: registers.0,
-            b: registers.1,
-            c: registers.2,
-            pointer: 0,
-            instructions: instructions_parsed,
-            orig: instructions,
-        })
+        separated_pair(parse_registers, "\n\n", parse_instructions)
+            .verify_map(|(registers, program)| State::new(registers, program).ok())
+            .parse_next(input)
     }
 
+    type Prepared = ();
+
     type Output1 = String;
 
     /// Part 1 took 2.3us
-    fn part_1(input: &Self::Input) -> Self::Output1 {
-        input.clone().map(|n| n.to_string()).join(",")
+    fn part_1(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output1> {
+        Ok(input.clone().map(|n| n.to_string()).join(","))
     }
 
     type Output2 = usize;
 
     /// Part 2 took 98.4us
-    fn part_2(input: &Self::Input) -> Self::Output2 {
-        find_input(input, 0, 0).unwrap()
+    fn part_2(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output2> {
+        find_input(input, 0, 0).context("no value of register A makes the program output itself")
+    }
+
+    /// Generate a program which outputs `size` values (at most 21)
+    fn generate(seed: u64, size: usize) -> Option<String> {
+        Some(generate_program(&mut fastrand::Rng::with_seed(seed), size))
     }
 }
 
@@ -235,12 +329,36 @@ Program: 0,3,5,4,3,0";
     #[test]
     fn test_part1() {
         let parsed = Day17::parser(&mut INPUT).unwrap();
-        assert_eq!(Day17::part_1(&parsed), "4,6,3,5,6,3,5,2,1,0".to_string());
+        assert_eq!(
+            Day17::part_1(&parsed, &()).unwrap(),
+            "4,6,3,5,6,3,5,2,1,0".to_string()
+        );
     }
 
     #[test]
     fn test_part2() {
         let parsed = Day17::parser(&mut INPUT2).unwrap();
-        assert_eq!(Day17::part_2(&parsed), 117440);
+        assert_eq!(Day17::part_2(&parsed, &()).unwrap(), 117440);
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = Day17::generate(42, 10).unwrap();
+        assert_eq!(Day17::generate(42, 10).unwrap(), input);
+        let parsed = Day17::parser(&mut input.as_str()).unwrap();
+        assert_eq!(Day17::part_1(&parsed, &()).unwrap().split(',').count(), 10);
+        let a = Day17::part_2(&parsed, &()).unwrap();
+        assert_eq!(parsed.with_register(a).collect_vec(), parsed.orig);
+    }
+
+    #[test]
+    fn test_new() {
+        let state = State::new((729, 0, 0), vec![0, 1, 5, 4, 3, 0]).unwrap();
+        assert_eq!(state.registers(), (729, 0, 0));
+        assert_eq!(state.program(), [0, 1, 5, 4, 3, 0]);
+        assert_eq!(state.collect_vec(), [4, 6, 3, 5, 6, 3, 5, 2, 1, 0]);
+        assert!(State::new((0, 0, 0), vec![0, 7]).is_err());
+        assert!(State::new((0, 0, 0), vec![8, 0]).is_err());
+        assert!(State::new((0, 0, 0), vec![0, 1, 5]).is_err());
     }
 }
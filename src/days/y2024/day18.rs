@@ -0,0 +1,282 @@
+use anyhow::{bail, Context as _, Result};
+use pathfinding::{grid::Grid, prelude::astar};
+use winnow::{
+    ascii::{dec_uint, line_ending},
+    combinator::separated,
+    error::StrContext,
+    seq, PResult, Parser as _,
+};
+
+use crate::days::{Day, Params};
+
+pub struct Day18;
+
+/// The falling bytes, and the parameters of the memory space
+#[derive(Debug, Clone)]
+pub struct Memory {
+    bytes: Vec<Pos>,
+    params: MemoryParams,
+}
+
+/// The parameters of the memory space, which are not part of the input
+///
+/// The example uses a 7x7 memory space and only considers the first 12 bytes for part 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryParams {
+    /// The size of the (square) memory space
+    grid_size: usize,
+    /// How many bytes have fallen for part 1
+    fallen: usize,
+}
+
+impl Default for MemoryParams {
+    fn default() -> Self {
+        Self {
+            grid_size: 71,
+            fallen: 1024,
+        }
+    }
+}
+
+impl Params for MemoryParams {
+    const NAMES: &'static [&'static str] = &["grid_size", "fallen"];
+
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "grid_size" => self.grid_size = value.parse()?,
+            "fallen" => self.fallen = value.parse()?,
+            _ => bail!("unknown parameter {name}"),
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Pos {
+    x: usize,
+    y: usize,
+}
+
+impl Pos {
+    fn distance(&self, other: &Pos) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    fn successors(&self, grid: &Grid) -> Vec<(Pos, usize)> {
+        grid.neighbours(self.into())
+            .into_iter()
+            .map(|p| (p.into(), 1))
+            .collect()
+    }
+}
+
+impl From<&Pos> for (usize, usize) {
+    fn from(value: &Pos) -> Self {
+        (value.x, value.y)
+    }
+}
+
+impl From<(usize, usize)> for Pos {
+    fn from(value: (usize, usize)) -> Self {
+        Self {
+            x: value.0,
+            y: value.1,
+        }
+    }
+}
+
+fn parse_pos(input: &mut &str) -> PResult<Pos> {
+    seq!(Pos {
+        x: dec_uint,
+        _: ',',
+        y: dec_uint
+    })
+    .context(StrContext::Label("byte position"))
+    .parse_next(input)
+}
+
+fn make_grid(obstacles: &[Pos], grid_size: usize) -> Grid {
+    let mut grid = Grid::new(grid_size, grid_size);
+    grid.fill();
+    for obs in obstacles {
+        grid.remove_vertex(obs.into());
+    }
+    grid
+}
+
+impl Day for Day18 {
+    const DAY: u32 = 18;
+
+    const TITLE: &'static str = "RAM Run";
+
+    const TAGS: &'static [&'static str] = &["grid", "pathfinding", "binary-search"];
+
+    type Input = Memory;
+
+    type Params = MemoryParams;
+
+    fn params(input: &mut Self::Input) -> Option<&mut Self::Params> {
+        Some(&mut input.params)
+    }
+
+    /// Parse the bytes, with the parameters of the real memory space
+    fn parser(input: &mut &str) -> PResult<Self::Input> {
+        let bytes = separated(1.., parse_pos, line_ending)
+            .context(StrContext::Label("bytes"))
+            .parse_next(input)?;
+        Ok(Memory {
+            bytes,
+            params: MemoryParams::default(),
+        })
+    }
+
+    type Prepared = ();
+
+    type Output1 = usize;
+
+    fn part_1(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output1> {
+        let goal: Pos = (input.params.grid_size - 1, input.params.grid_size - 1).into();
+        let grid = make_grid(
+            input
+                .bytes
+                .get(0..input.params.fallen)
+                .context("not enough bytes in the input")?,
+            input.params.grid_size,
+        );
+        let (_, score) = astar(
+            &Pos { x: 0, y: 0 },
+            |p| p.successors(&grid),
+            |p| p.distance(&goal),
+            |p| *p == goal,
+        )
+        .context("the exit is not reachable")?;
+        Ok(score)
+    }
+
+    type Output2 = String;
+
+    fn part_2(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output2> {
+        let start = (0, 0);
+        let goal = (input.params.grid_size - 1, input.params.grid_size - 1);
+        let (first, second) = input
+            .bytes
+            .split_at_checked(input.params.fallen)
+            .context("not enough bytes in the input")?;
+        if second.is_empty() {
+            bail!("no byte falls after the first {}", input.params.fallen);
+        }
+        let grid = make_grid(first, input.params.grid_size);
+        // binary search
+        // index into the second half of the pieces
+        let mut left = 0;
+        let mut right = second.len() - 1;
+        while left < right {
+            let mut grid = grid.clone();
+            let m = (left + right) / 2;
+            // add obstacles with indices up to and including m
+            for obs in second.get(0..=m).unwrap() {
+                grid.remove_vertex(obs.into());
+            }
+            if grid.dfs_reachable(start, |_| true).contains(&goal) {
+                // if we can still reach the exit, we move the left bound past m
+                left = m + 1;
+            } else {
+                // else m might be the first piece which cuts off the exit, we move the right bound to m
+                right = m;
+            }
+        }
+        // when left >= right, we found the first piece which cuts off the exit
+        let obs = second.get(left).unwrap();
+        Ok(format!("{},{}", obs.x, obs.y))
+    }
+}
+
+#[cfg(test)]
+#[allow(const_item_mutation)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "5,4
+4,2
+4,5
+3,0
+2,1
+6,3
+2,4
+1,5
+0,6
+3,3
+2,6
+5,1
+1,2
+5,5
+2,5
+6,5
+1,4
+0,4
+6,4
+1,1
+6,1
+1,0
+0,5
+1,6
+2,0";
+
+    /// A wall with a gap at the bottom, which the 14th byte closes right in the middle of the binary search
+    const WALL: &str = "3,0
+3,1
+3,2
+3,3
+3,4
+3,5
+6,0
+5,0
+6,1
+5,1
+0,6
+1,6
+0,5
+3,6
+1,5";
+
+    /// The parameters of the example memory space
+    const EXAMPLE: MemoryParams = MemoryParams {
+        grid_size: 7,
+        fallen: 12,
+    };
+
+    fn parse(mut input: &str) -> Memory {
+        let mut parsed = Day18::parser(&mut input).unwrap();
+        parsed.params = EXAMPLE;
+        parsed
+    }
+
+    #[test]
+    fn test_params() {
+        let params = ["grid_size=7".parse().unwrap(), "fallen=12".parse().unwrap()];
+        assert_eq!(Day18::parse_input(INPUT, &params).unwrap().params, EXAMPLE);
+        let unknown = Day18::parse_input(INPUT, &["size=7".parse().unwrap()]).unwrap_err();
+        assert_eq!(
+            unknown.to_string(),
+            "day 18 has no parameter size (available: grid_size, fallen)"
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        let parsed = parse(INPUT);
+        assert_eq!(Day18::part_1(&parsed, &()).unwrap(), 22);
+    }
+
+    #[test]
+    fn test_part2() {
+        let parsed = parse(INPUT);
+        assert_eq!(Day18::part_2(&parsed, &()).unwrap(), "6,1");
+    }
+
+    #[test]
+    fn test_part2_middle() {
+        let parsed = parse(WALL);
+        assert_eq!(Day18::part_2(&parsed, &()).unwrap(), "3,6");
+    }
+}
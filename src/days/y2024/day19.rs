@@ -1,6 +1,8 @@
+use anyhow::Result;
 use winnow::{
     ascii::{alpha1, line_ending},
     combinator::separated,
+    error::StrContext,
     seq, PResult, Parser as _,
 };
 
@@ -18,12 +20,16 @@ pub struct Puzzle {
 
 /// Parse the available towel patterns
 fn parse_available(input: &mut &str) -> PResult<Vec<String>> {
-    separated(1.., alpha1.map(|s: &str| s.to_string()), ", ").parse_next(input)
+    separated(1.., alpha1.map(|s: &str| s.to_string()), ", ")
+        .context(StrContext::Label("towel patterns"))
+        .parse_next(input)
 }
 
 /// Parse the desired towel arrangements
 fn parse_desired(input: &mut &str) -> PResult<Vec<String>> {
-    separated(1.., alpha1.map(|s: &str| s.to_string()), line_ending).parse_next(input)
+    separated(1.., alpha1.map(|s: &str| s.to_string()), line_ending)
+        .context(StrContext::Label("designs"))
+        .parse_next(input)
 }
 
 /// Count how many ways there are to arrange available towels into the desired arrangement
@@ -48,8 +54,16 @@ fn count_combinations(
 }
 
 impl Day for Day19 {
+    const DAY: u32 = 19;
+
+    const TITLE: &'static str = "Linen Layout";
+
+    const TAGS: &'static [&'static str] = &["memoization", "recursion"];
+
     type Input = Puzzle;
 
+    type Params = ();
+
     fn parser(input: &mut &str) -> PResult<Self::Input> {
         seq!(Puzzle {
             available: parse_available,
@@ -59,28 +73,30 @@ impl Day for Day19 {
         .parse_next(input)
     }
 
+    type Prepared = ();
+
     type Output1 = usize;
 
     /// Part 1 took 2.17ms
-    fn part_1(input: &Self::Input) -> Self::Output1 {
+    fn part_1(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output1> {
         let mut cache = HashMap::default();
-        input
+        Ok(input
             .desired
             .iter()
             .filter(|d| count_combinations(d, &input.available, &mut cache) > 0)
-            .count()
+            .count())
     }
 
     type Output2 = usize;
 
     /// Part 2 tool 18.3ms
-    fn part_2(input: &Self::Input) -> Self::Output2 {
+    fn part_2(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output2> {
         let mut cache = HashMap::default();
-        input
+        Ok(input
             .desired
             .iter()
             .map(|d| count_combinations(d, &input.available, &mut cache))
-            .sum()
+            .sum())
     }
 }
 
@@ -103,12 +119,12 @@ bbrgwb";
     #[test]
     fn test_part1() {
         let parsed = Day19::parser(&mut INPUT).unwrap();
-        assert_eq!(Day19::part_1(&parsed), 6);
+        assert_eq!(Day19::part_1(&parsed, &()).unwrap(), 6);
     }
 
     #[test]
     fn test_part2() {
         let parsed = Day19::parser(&mut INPUT).unwrap();
-        assert_eq!(Day19::part_2(&parsed), 16);
+        assert_eq!(Day19::part_2(&parsed, &()).unwrap(), 16);
     }
 }
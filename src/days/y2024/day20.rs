@@ -0,0 +1,271 @@
+use std::collections::VecDeque;
+
+use anyhow::{bail, Result};
+use pathfinding::grid::Grid;
+use winnow::{
+    ascii::line_ending,
+    combinator::{repeat, separated},
+    error::StrContext,
+    token::one_of,
+    PResult, Parser as _,
+};
+
+const DIRS: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+use crate::days::{Day, Params};
+
+pub type IndexSet<K> = indexmap::set::IndexSet<K, ahash::RandomState>;
+pub type HashSet<K> = std::collections::HashSet<K, ahash::RandomState>;
+pub type Pos = (usize, usize);
+
+pub struct Day20;
+
+/// Puzzle input
+#[derive(Debug, Clone)]
+pub struct Race {
+    grid: Grid,
+    start: Pos,
+    end: Pos,
+    /// The number of tiles in a row of the racetrack
+    width: usize,
+    /// The number of rows of the racetrack
+    height: usize,
+    params: RaceParams,
+}
+
+/// The parameters of the race, which are not part of the input
+///
+/// In the example, cheats saving at least 50 picoseconds are counted instead of 100.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RaceParams {
+    /// The minimum time a cheat must save to be counted
+    savings_limit: usize,
+}
+
+impl Default for RaceParams {
+    fn default() -> Self {
+        Self { savings_limit: 100 }
+    }
+}
+
+impl Params for RaceParams {
+    const NAMES: &'static [&'static str] = &["savings_limit"];
+
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "savings_limit" => self.savings_limit = value.parse()?,
+            _ => bail!("unknown parameter {name}"),
+        }
+        Ok(())
+    }
+}
+
+/// Parse a line of the racetrack
+fn parse_line(input: &mut &str) -> PResult<Vec<char>> {
+    repeat(1.., one_of(('#', '.', 'E', 'S')))
+        .context(StrContext::Label("row"))
+        .parse_next(input)
+}
+
+/// Parse the track into a list of list of characters
+fn parse_grid(input: &mut &str) -> PResult<Vec<Vec<char>>> {
+    separated(1.., parse_line, line_ending)
+        .context(StrContext::Label("racetrack"))
+        .parse_next(input)
+}
+
+/// Get the ordered list of racetrack coordinates
+fn get_track(race: &Race) -> IndexSet<Pos> {
+    let mut track = IndexSet::default();
+    track.insert(race.start);
+    let mut current = race.start;
+    while current != race.end {
+        for n in race.grid.neighbours(current) {
+            if track.contains(&n) {
+                continue;
+            }
+            track.insert(n);
+            current = n;
+            break;
+        }
+    }
+    track
+}
+
+/// Count the possible cheats starting at `pos` with maximum `moves` steps
+fn count_possible_cheats(pos: Pos, race: &Race, track: &IndexSet<Pos>, moves: usize) -> usize {
+    let curr_time = track.get_index_of(&pos).unwrap(); // time at which we reach `pos`
+    let mut count = 0;
+    let mut seen = HashSet::<Pos>::default(); // visited coordinates
+    seen.insert(pos);
+    let mut candidates = VecDeque::from([(pos, moves)]); // candidates for DFS
+    while let Some((candidate, rem_moves)) = candidates.pop_front() {
+        // if the candidate lies on the track, we check if the cheat makes us gain at least 100ps
+        // the index into the racetrack list is the time when we visit that location
+        if let Some(time) = track.get_index_of(&candidate) {
+            let steps = moves - rem_moves; // need to subtract the length of the cheat
+            if time.saturating_sub(curr_time).saturating_sub(steps) >= race.params.savings_limit {
+                count += 1;
+            }
+        }
+        // if we reached the maximum number of cheat steps, we can't go further
+        if rem_moves == 0 {
+            continue;
+        }
+        // check which of the four neighbours we can visit (inside the grid)
+        let neighbours = DIRS.iter().filter_map(|(dx, dy)| {
+            let x = candidate.0 as isize + dx;
+            let y = candidate.1 as isize + dy;
+            if x < 0 || y < 0 || x as usize >= race.width || y as usize >= race.height {
+                return None;
+            }
+            Some((x as usize, y as usize))
+        });
+        for (x, y) in neighbours {
+            // for each neighbour we haven't visited yet, we add it to the DFS list
+            if !seen.contains(&(x, y)) {
+                candidates.push_back(((x, y), rem_moves - 1));
+                seen.insert((x, y));
+            }
+        }
+    }
+    count
+}
+
+impl Day for Day20 {
+    const DAY: u32 = 20;
+
+    const TITLE: &'static str = "Race Condition";
+
+    const TAGS: &'static [&'static str] = &["grid", "bfs"];
+
+    type Input = Race;
+
+    type Params = RaceParams;
+
+    fn params(input: &mut Self::Input) -> Option<&mut Self::Params> {
+        Some(&mut input.params)
+    }
+
+    /// Parse the racetrack
+    fn parser(input: &mut &str) -> PResult<Self::Input> {
+        let mut start = (0, 0);
+        let mut end = (0, 0);
+        let rows = parse_grid.parse_next(input)?;
+        let width = rows[0].len();
+        let height = rows.len();
+        let grid: Grid = rows
+            .into_iter()
+            .enumerate()
+            .flat_map(|(y, row)| row.into_iter().enumerate().map(move |(x, c)| (x, y, c)))
+            .filter_map(|(x, y, c)| match c {
+                'S' => {
+                    start = (x, y);
+                    Some((x, y))
+                }
+                'E' => {
+                    end = (x, y);
+                    Some((x, y))
+                }
+                '.' => Some((x, y)),
+                '#' => None,
+                _ => unreachable!(),
+            })
+            .collect();
+        Ok(Race {
+            grid,
+            start,
+            end,
+            width,
+            height,
+            params: RaceParams::default(),
+        })
+    }
+
+    /// The ordered list of racetrack coordinates
+    type Prepared = IndexSet<Pos>;
+
+    fn prepare(input: &Self::Input) -> Result<Self::Prepared> {
+        Ok(get_track(input))
+    }
+
+    type Output1 = usize;
+
+    /// Part 1 took 4.27ms
+    fn part_1(input: &Self::Input, track: &Self::Prepared) -> Result<Self::Output1> {
+        Ok(track
+            .iter()
+            .map(|pos| count_possible_cheats(*pos, input, track, 2))
+            .sum())
+    }
+
+    type Output2 = usize;
+
+    /// Part 2 took 227.8ms
+    fn part_2(input: &Self::Input, track: &Self::Prepared) -> Result<Self::Output2> {
+        Ok(track
+            .iter()
+            .map(|pos| count_possible_cheats(*pos, input, track, 20))
+            .sum())
+    }
+}
+
+#[cfg(test)]
+#[allow(const_item_mutation)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############";
+
+    /// Only cheats saving at least 50 picoseconds are counted in the example
+    const EXAMPLE: RaceParams = RaceParams { savings_limit: 50 };
+
+    #[test]
+    fn test_part1() {
+        let mut parsed = Day20::parser(&mut INPUT).unwrap();
+        parsed.params = EXAMPLE;
+        let track = Day20::prepare(&parsed).unwrap();
+        assert_eq!(Day20::part_1(&parsed, &track).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_part2() {
+        let mut parsed = Day20::parser(&mut INPUT).unwrap();
+        parsed.params = EXAMPLE;
+        let track = Day20::prepare(&parsed).unwrap();
+        assert_eq!(Day20::part_2(&parsed, &track).unwrap(), 285);
+    }
+
+    #[test]
+    fn test_tall_grid() {
+        let mut input = "####
+#S.#
+##.#
+#..#
+#.##
+#..#
+##.#
+#E.#
+####";
+        let mut parsed = Day20::parser(&mut input).unwrap();
+        assert_eq!((parsed.width, parsed.height), (4, 9));
+        parsed.params = RaceParams { savings_limit: 2 };
+        let track = Day20::prepare(&parsed).unwrap();
+        assert_eq!(track.last(), Some(&(1, 7)));
+        assert_eq!(Day20::part_1(&parsed, &track).unwrap(), 3);
+    }
+}
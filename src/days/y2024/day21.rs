@@ -1,10 +1,12 @@
 use std::iter::once;
 
+use anyhow::Result;
 use itertools::Itertools;
 use pathfinding::{grid::Grid, prelude::astar_bag_collect};
 use winnow::{
     ascii::line_ending,
     combinator::{repeat, separated},
+    error::StrContext,
     token::one_of,
     PResult, Parser as _,
 };
@@ -120,7 +122,9 @@ impl From<&Dirpad> for Pos {
 
 /// Parse a sequence of numpad keys
 fn parse_seq(input: &mut &str) -> PResult<Vec<Numpad>> {
-    let chars: Vec<_> = repeat(4, one_of('0'..='A')).parse_next(input)?;
+    let chars: Vec<_> = repeat(4, one_of('0'..='A'))
+        .context(StrContext::Label("code"))
+        .parse_next(input)?;
     Ok(chars.into_iter().map(Into::into).collect())
 }
 
@@ -239,63 +243,84 @@ fn code_to_num(code: &[Numpad]) -> usize {
     str.parse().unwrap()
 }
 
+/// The cost of moving between each pair of keys of the numeric keypad, pressing it included
+#[derive(Debug, Clone, Default)]
+pub struct CostMaps {
+    /// When there are 2 robots using directional keypads
+    two_robots: HashMap<(Numpad, Numpad), usize>,
+    /// When there are 25 robots using directional keypads
+    twenty_five_robots: HashMap<(Numpad, Numpad), usize>,
+}
+
+/// Sum the complexities of the codes, knowing the cost of each move on the numeric keypad
+///
+/// For each code sequence, calculate the minimum cost to input it (adding a move from the initial A key).
+fn complexity(codes: &[Vec<Numpad>], cost: &HashMap<(Numpad, Numpad), usize>) -> usize {
+    codes
+        .iter()
+        .map(|code| {
+            let len: usize = once(&Numpad::A)
+                .chain(code.iter())
+                .tuple_windows()
+                .map(|(a, b)| cost.get(&(*a, *b)).unwrap())
+                .sum();
+            code_to_num(code) * len
+        })
+        .sum()
+}
+
 impl Day for Day21 {
+    const DAY: u32 = 21;
+
+    const TITLE: &'static str = "Keypad Conundrum";
+
+    const TAGS: &'static [&'static str] = &["pathfinding", "dynamic-programming"];
+
     type Input = Vec<Vec<Numpad>>;
 
+    type Params = ();
+
     fn parser(input: &mut &str) -> PResult<Self::Input> {
-        separated(1.., parse_seq, line_ending).parse_next(input)
+        separated(1.., parse_seq, line_ending)
+            .context(StrContext::Label("codes"))
+            .parse_next(input)
     }
 
-    type Output1 = usize;
+    /// The cost maps of the numeric keypad for 2 and 25 intermediate robots
+    type Prepared = CostMaps;
 
-    /// Part 1 took 199.3us
-    fn part_1(input: &Self::Input) -> Self::Output1 {
+    fn prepare(_input: &Self::Input) -> Result<Self::Prepared> {
         let dir_keypad = make_dir_keypad();
         let num_keypad = make_numeric_keypad();
         let mut cache = HashMap::default();
         // construct the cost maps of each move at each level
-        let cost = move_cost(&dir_keypad, &DIRPAD, None, &mut cache);
-        let cost = move_cost(&dir_keypad, &DIRPAD, Some(&cost), &mut cache);
-        let cost = move_cost(&num_keypad, &NUMPAD, Some(&cost), &mut cache);
-        // for each code sequence, calculate the minimum cost to input it (adding a move from the initial A key)
-        input
-            .iter()
-            .map(|code| {
-                let len: usize = once(&Numpad::A)
-                    .chain(code.iter())
-                    .tuple_windows()
-                    .map(|(a, b)| cost.get(&(*a, *b)).unwrap())
-                    .sum();
-                code_to_num(code) * len
-            })
-            .sum()
+        let mut cost = move_cost(&dir_keypad, &DIRPAD, None, &mut cache);
+        let mut two_robots = HashMap::default();
+        for level in 2..=25 {
+            cost = move_cost(&dir_keypad, &DIRPAD, Some(&cost), &mut cache);
+            if level == 2 {
+                two_robots = move_cost(&num_keypad, &NUMPAD, Some(&cost), &mut cache);
+            }
+        }
+        let twenty_five_robots = move_cost(&num_keypad, &NUMPAD, Some(&cost), &mut cache);
+        Ok(CostMaps {
+            two_robots,
+            twenty_five_robots,
+        })
+    }
+
+    type Output1 = usize;
+
+    /// Part 1 took 199.3us
+    fn part_1(input: &Self::Input, cost_maps: &Self::Prepared) -> Result<Self::Output1> {
+        Ok(complexity(input, &cost_maps.two_robots))
     }
 
     type Output2 = usize;
 
     /// Part 2 took 209.7us
-    fn part_2(input: &Self::Input) -> Self::Output2 {
-        let dir_keypad = make_dir_keypad();
-        let num_keypad = make_numeric_keypad();
-        let mut cache = HashMap::default();
-        // construct the cost maps of each move at each level
-        let mut cost = move_cost(&dir_keypad, &DIRPAD, None, &mut cache);
-        for _ in 0..24 {
-            cost = move_cost(&dir_keypad, &DIRPAD, Some(&cost), &mut cache);
-        }
-        let cost = move_cost(&num_keypad, &NUMPAD, Some(&cost), &mut cache);
-        // for each code sequence, calculate the minimum cost to input it (adding a move from the initial A key)
-        input
-            .iter()
-            .map(|code| {
-                let len: usize = once(&Numpad::A)
-                    .chain(code.iter())
-                    .tuple_windows()
-                    .map(|(a, b)| cost.get(&(*a, *b)).unwrap())
-                    .sum();
-                code_to_num(code) * len
-            })
-            .sum()
+    fn part_2(input: &Self::Input, cost_maps: &Self::Prepared) -> Result<Self::Output2> {
+        Ok(complexity(input, &cost_maps.twenty_five_robots))
     }
 }
 
@@ -313,6 +338,7 @@ mod tests {
     #[test]
     fn test_part1() {
         let parsed = Day21::parser(&mut INPUT).unwrap();
-        assert_eq!(Day21::part_1(&parsed), 126384);
+        let cost_maps = Day21::prepare(&parsed).unwrap();
+        assert_eq!(Day21::part_1(&parsed, &cost_maps).unwrap(), 126384);
     }
 }
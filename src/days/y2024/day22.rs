@@ -1,7 +1,9 @@
+use anyhow::Result;
 use itertools::Itertools;
 use winnow::{
     ascii::{dec_uint, line_ending},
     combinator::separated,
+    error::StrContext,
     PResult, Parser as _,
 };
 
@@ -53,23 +55,35 @@ fn sequences(seed: usize) -> HashMap<(isize, isize, isize, isize), usize> {
 }
 
 impl Day for Day22 {
+    const DAY: u32 = 22;
+
+    const TITLE: &'static str = "Monkey Market";
+
+    const TAGS: &'static [&'static str] = &["prng", "hashing"];
+
     type Input = Vec<usize>;
 
+    type Params = ();
+
     fn parser(input: &mut &str) -> PResult<Self::Input> {
-        separated(1.., dec_uint::<_, usize, _>, line_ending).parse_next(input)
+        separated(1.., dec_uint::<_, usize, _>, line_ending)
+            .context(StrContext::Label("secret numbers"))
+            .parse_next(input)
     }
 
+    type Prepared = ();
+
     type Output1 = usize;
 
     /// Part 1 took 5.2ms
-    fn part_1(input: &Self::Input) -> Self::Output1 {
-        input.iter().map(|n| nth_number(*n, 2000)).sum()
+    fn part_1(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output1> {
+        Ok(input.iter().map(|n| nth_number(*n, 2000)).sum())
     }
 
     type Output2 = usize;
 
     /// Part 2 took 102.05ms
-    fn part_2(input: &Self::Input) -> Self::Output2 {
+    fn part_2(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output2> {
         // collect the total number of bananas one would get depending on the given diff sequence
         let mut bananas = HashMap::<(isize, isize, isize, isize), usize>::default();
         for n in input {
@@ -82,7 +96,7 @@ impl Day for Day22 {
             }
         }
         // the maximum of the values in the hashmap is our answer
-        *bananas.values().max().unwrap()
+        Ok(*bananas.values().max().unwrap())
     }
 }
 
@@ -104,12 +118,12 @@ mod tests {
     #[test]
     fn test_part1() {
         let parsed = Day22::parser(&mut INPUT).unwrap();
-        assert_eq!(Day22::part_1(&parsed), 37327623);
+        assert_eq!(Day22::part_1(&parsed, &()).unwrap(), 37327623);
     }
 
     #[test]
     fn test_part2() {
         let parsed = Day22::parser(&mut INPUT2).unwrap();
-        assert_eq!(Day22::part_2(&parsed), 23);
+        assert_eq!(Day22::part_2(&parsed, &()).unwrap(), 23);
     }
 }
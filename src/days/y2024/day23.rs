@@ -1,10 +1,12 @@
 use std::iter::once;
 
+use anyhow::Result;
 use itertools::Itertools;
 use petgraph::prelude::*;
 use winnow::{
     ascii::{alpha1, line_ending},
     combinator::{separated, separated_pair},
+    error::StrContext,
     PResult, Parser as _,
 };
 
@@ -15,11 +17,15 @@ pub type HashMap<K, T> = std::collections::HashMap<K, T, ahash::RandomState>;
 pub struct Day23;
 
 fn parse_pair<'a>(input: &mut &'a str) -> PResult<(&'a str, &'a str)> {
-    separated_pair(alpha1, '-', alpha1).parse_next(input)
+    separated_pair(alpha1, '-', alpha1)
+        .context(StrContext::Label("connection"))
+        .parse_next(input)
 }
 
 fn parse_pairs<'a>(input: &mut &'a str) -> PResult<Vec<(&'a str, &'a str)>> {
-    separated(1.., parse_pair, line_ending).parse_next(input)
+    separated(1.., parse_pair, line_ending)
+        .context(StrContext::Label("connections"))
+        .parse_next(input)
 }
 
 #[derive(Debug)]
@@ -29,8 +35,16 @@ pub struct Puzzle {
 }
 
 impl Day for Day23 {
+    const DAY: u32 = 23;
+
+    const TITLE: &'static str = "LAN Party";
+
+    const TAGS: &'static [&'static str] = &["graph", "cliques"];
+
     type Input = Puzzle;
 
+    type Params = ();
+
     fn parser(input: &mut &str) -> PResult<Self::Input> {
         let edges = parse_pairs.parse_next(input)?;
         let mut graph = UnGraph::new_undirected();
@@ -47,10 +61,12 @@ impl Day for Day23 {
         Ok(Puzzle { graph, nodes })
     }
 
+    type Prepared = ();
+
     type Output1 = usize;
 
-    fn part_1(input: &Self::Input) -> Self::Output1 {
-        input
+    fn part_1(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output1> {
+        Ok(input
             .nodes
             .values()
             .combinations(3)
@@ -61,12 +77,12 @@ impl Day for Day23 {
                     && n.iter()
                         .any(|idx| input.graph.node_weight(**idx).unwrap().starts_with("t"))
             })
-            .count()
+            .count())
     }
 
     type Output2 = String;
 
-    fn part_2(input: &Self::Input) -> Self::Output2 {
+    fn part_2(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output2> {
         let mut largest_group = Vec::new();
         for idx in input.nodes.values() {
             for group in input
@@ -93,7 +109,7 @@ impl Day for Day23 {
             .cloned()
             .collect_vec();
         nodes.sort_unstable();
-        nodes.join(",")
+        Ok(nodes.join(","))
     }
 }
 
@@ -138,12 +154,15 @@ td-yn";
     #[test]
     fn test_part1() {
         let parsed = Day23::parser(&mut INPUT).unwrap();
-        assert_eq!(Day23::part_1(&parsed), 7);
+        assert_eq!(Day23::part_1(&parsed, &()).unwrap(), 7);
     }
 
     #[test]
     fn test_part2() {
         let parsed = Day23::parser(&mut INPUT).unwrap();
-        assert_eq!(Day23::part_2(&parsed), "co,de,ka,ta".to_string());
+        assert_eq!(
+            Day23::part_2(&parsed, &()).unwrap(),
+            "co,de,ka,ta".to_string()
+        );
     }
 }
@@ -1,11 +1,14 @@
+use anyhow::Result;
+use enumflags2::{make_bitflags, BitFlags};
 use itertools::Itertools;
 use winnow::{
     combinator::{alt, preceded, repeat, separated},
+    error::StrContext,
     token::{one_of, take},
     PResult, Parser as _,
 };
 
-use crate::days::Day;
+use crate::days::{Day, Part};
 
 pub struct Day25;
 
@@ -21,12 +24,16 @@ fn parse_lock(input: &mut &str) -> PResult<Pins> {
     let mut out = vec![0; 5];
     for i in 0..5 {
         let prec = if i == 0 { "#####\n" } else { "\n" };
-        let height: Vec<_> = preceded(prec, repeat(5, one_of(('.', '#')))).parse_next(input)?;
+        let height: Vec<_> = preceded(prec, repeat(5, one_of(('.', '#'))))
+            .context(StrContext::Label("lock"))
+            .parse_next(input)?;
         height.into_iter().enumerate().for_each(|(i, p)| {
             out[i] += (p == '#') as u8;
         });
     }
-    take(6usize).parse_next(input)?; // consume the last row
+    take(6usize)
+        .context(StrContext::Label("lock"))
+        .parse_next(input)?; // consume the last row
     Ok(Pins::Lock(out))
 }
 
@@ -35,12 +42,16 @@ fn parse_key(input: &mut &str) -> PResult<Pins> {
     let mut out = vec![0; 5];
     for i in 0..5 {
         let prec = if i == 0 { ".....\n" } else { "\n" };
-        let height: Vec<_> = preceded(prec, repeat(5, one_of(('.', '#')))).parse_next(input)?;
+        let height: Vec<_> = preceded(prec, repeat(5, one_of(('.', '#'))))
+            .context(StrContext::Label("key"))
+            .parse_next(input)?;
         height.into_iter().enumerate().for_each(|(i, p)| {
             out[i] += (p == '#') as u8;
         });
     }
-    take(6usize).parse_next(input)?; // consume the last row
+    take(6usize)
+        .context(StrContext::Label("key"))
+        .parse_next(input)?; // consume the last row
     Ok(Pins::Key(out))
 }
 
@@ -58,12 +69,23 @@ fn overlaps(lock: &[u8], key: &[u8]) -> bool {
 }
 
 impl Day for Day25 {
+    const DAY: u32 = 25;
+
+    const TITLE: &'static str = "Code Chronicle";
+
+    const TAGS: &'static [&'static str] = &["parsing", "brute-force"];
+
+    const PARTS: BitFlags<Part> = make_bitflags!(Part::One);
+
     type Input = Puzzle;
 
+    type Params = ();
+
     /// Parse keys and locks into the puzzle input struct
     fn parser(input: &mut &str) -> PResult<Self::Input> {
-        let items: Vec<_> =
-            separated(1.., alt((parse_lock, parse_key)), "\n\n").parse_next(input)?;
+        let items: Vec<_> = separated(1.., alt((parse_lock, parse_key)), "\n\n")
+            .context(StrContext::Label("schematics"))
+            .parse_next(input)?;
         let mut locks = Vec::new();
         let mut keys = Vec::new();
         items.into_iter().for_each(|p| match p {
@@ -73,22 +95,24 @@ impl Day for Day25 {
         Ok(Puzzle { locks, keys })
     }
 
+    type Prepared = ();
+
     type Output1 = usize;
 
     /// Part 1 took 212.3us
-    fn part_1(input: &Self::Input) -> Self::Output1 {
-        input
+    fn part_1(input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output1> {
+        Ok(input
             .locks
             .iter()
             .cartesian_product(input.keys.iter())
             .filter(|(lock, key)| !overlaps(lock, key))
-            .count()
+            .count())
     }
 
-    type Output2 = usize;
-
     /// No part 2!
-    fn part_2(_input: &Self::Input) -> Self::Output2 {
-        0
+    type Output2 = ();
+
+    fn part_2(_input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output2> {
+        Ok(())
     }
 }
@@ -0,0 +1,160 @@
+use std::fmt::{Display, Write as _};
+
+use winnow::error::{ContextError, ParseError, StrContext};
+
+/// A parse failure, with its location in the input and the context labels attached by the parsers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseFailure {
+    /// Line number (starting at 1)
+    pub line: usize,
+    /// Column number in characters (starting at 1)
+    pub column: usize,
+    /// The offending line, with control characters escaped
+    pub snippet: String,
+    /// The position of the caret below the snippet, accounting for the escaped characters
+    caret: usize,
+    /// The labels of the parsers which failed, from the outermost to the innermost
+    pub labels: Vec<&'static str>,
+    /// What the parser expected to find at this location
+    pub expected: Vec<String>,
+}
+
+impl ParseFailure {
+    /// Locate the error in the `input` that was being parsed
+    pub fn new(input: &str, error: &ParseError<&str, ContextError>) -> Self {
+        let offset = error.offset().min(input.len());
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        let before = &input[line_start..offset];
+        let mut labels = Vec::new();
+        let mut expected = Vec::new();
+        for context in error.inner().context() {
+            match context {
+                StrContext::Label(label) => labels.push(*label),
+                StrContext::Expected(value) => expected.push(value.to_string()),
+                _ => {}
+            }
+        }
+        labels.reverse();
+        Self {
+            line: input[..offset].matches('\n').count() + 1,
+            column: before.chars().count() + 1,
+            snippet: escape(&input[line_start..line_end]),
+            caret: escape(before).chars().count(),
+            labels,
+            expected,
+        }
+    }
+}
+
+/// Make the control characters of a line visible (e.g. a carriage return becomes `\r`)
+fn escape(line: &str) -> String {
+    line.chars().fold(String::new(), |mut out, c| {
+        match c {
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "{}", c.escape_unicode());
+            }
+            c => out.push(c),
+        }
+        out
+    })
+}
+
+impl Display for ParseFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "parse error at line {}, column {}",
+            self.line, self.column
+        )?;
+        let margin = " ".repeat(self.line.to_string().len());
+        writeln!(f, "{margin} |")?;
+        writeln!(f, "{} | {}", self.line, self.snippet)?;
+        write!(f, "{margin} | {}^", " ".repeat(self.caret))?;
+        if self.labels.is_empty() && self.expected.is_empty() {
+            return write!(f, "\nthe input could only be parsed up to here");
+        }
+        if !self.labels.is_empty() {
+            write!(f, "\nwhile parsing {}", self.labels.join(" > "))?;
+        }
+        if !self.expected.is_empty() {
+            write!(f, "\nexpected {}", self.expected.join(" or "))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseFailure {}
+
+#[cfg(test)]
+mod tests {
+    use winnow::{
+        ascii::{dec_uint, line_ending},
+        combinator::{preceded, separated},
+        error::StrContextValue,
+        PResult, Parser,
+    };
+
+    use super::*;
+
+    fn parse_register(input: &mut &str) -> PResult<u32> {
+        preceded(
+            (
+                "Register ",
+                'A'.context(StrContext::Expected(StrContextValue::CharLiteral('A'))),
+                ": ",
+            ),
+            dec_uint,
+        )
+        .context(StrContext::Label("register"))
+        .parse_next(input)
+    }
+
+    fn parse_registers(input: &mut &str) -> PResult<Vec<u32>> {
+        separated(2, parse_register, line_ending)
+            .context(StrContext::Label("registers"))
+            .parse_next(input)
+    }
+
+    fn parse_failure(input: &str) -> ParseFailure {
+        let error = parse_registers.parse(input).unwrap_err();
+        ParseFailure::new(input, &error)
+    }
+
+    #[test]
+    fn test_context() {
+        let failure = parse_failure("Register A: 1\nRegister B: 2");
+        assert_eq!((failure.line, failure.column), (2, 10));
+        assert_eq!(failure.labels, ["registers", "register"]);
+        assert_eq!(
+            failure.to_string(),
+            "parse error at line 2, column 10
+  |
+2 | Register B: 2
+  |          ^
+while parsing registers > register
+expected `A`"
+        );
+    }
+
+    #[test]
+    fn test_escape() {
+        let failure = parse_failure("Register A:\t1\nRegister A: 2");
+        assert_eq!((failure.line, failure.column), (1, 11));
+        assert_eq!(failure.snippet, "Register A:\\t1");
+        let failure = parse_failure("Register A: 1\nRegister A: 2\r");
+        assert_eq!((failure.line, failure.column), (2, 14));
+        assert_eq!(
+            failure.to_string(),
+            "parse error at line 2, column 14
+  |
+2 | Register A: 2\\r
+  |              ^
+the input could only be parsed up to here"
+        );
+    }
+}
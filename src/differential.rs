@@ -0,0 +1,278 @@
+//! Differential testing of the solutions against their reference implementations
+//!
+//! The inputs are either generated with [`Day::generate`] or obtained by randomly mutating an example. When a part
+//! disagrees with its reference implementation, the input is shrunk to a minimal one which still shows the mismatch.
+use std::{fmt::Display, ops::RangeInclusive};
+
+use anyhow::{bail, Result};
+
+use crate::{
+    answer::Answer,
+    days::{catch_panic, Day, Part},
+};
+
+/// How many times a failing input can be shrunk, to make sure we always terminate
+const MAX_SHRINK_STEPS: usize = 10_000;
+
+/// An input for which a part and its reference implementation disagree
+#[derive(Debug, Clone)]
+pub struct Mismatch {
+    pub part: Part,
+    pub input: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "part {} disagrees with its reference implementation",
+            self.part.number()
+        )?;
+        writeln!(f, "expected: {}", self.expected)?;
+        writeln!(f, "actual: {}", self.actual)?;
+        write!(f, "input:\n{}", self.input)
+    }
+}
+
+/// The answer of the reference implementation of a part, if there is one
+fn reference<D: Day>(input: &D::Input, part: Part) -> Option<Result<Answer>> {
+    match part {
+        Part::One => D::reference_part_1(input).map(|answer| Ok(answer?.into())),
+        Part::Two => D::reference_part_2(input).map(|answer| Ok(answer?.into())),
+    }
+}
+
+/// The answer of the (fast) implementation of a part, including the preparation step
+fn solve<D: Day>(input: &D::Input, part: Part) -> Result<Answer> {
+    let prepared = D::prepare(input)?;
+    match part {
+        Part::One => Ok(D::part_1(input, &prepared)?.into()),
+        Part::Two => Ok(D::part_2(input, &prepared)?.into()),
+    }
+}
+
+/// Compare the implemented parts with their reference implementations on an input
+///
+/// Inputs which can't be parsed, or which are rejected by the reference implementation, are skipped and give `None`.
+fn compare<D: Day>(input_string: &str) -> Option<Result<(), Mismatch>> {
+    let input = catch_panic(|| D::parse_input(input_string, &[])).ok()?;
+    let mut compared = false;
+    for part in D::PARTS {
+        let Ok(Some(expected)) = catch_panic(|| reference::<D>(&input, part).transpose()) else {
+            continue;
+        };
+        compared = true;
+        let actual = match catch_panic(|| solve::<D>(&input, part)) {
+            Ok(answer) if answer == expected => continue,
+            Ok(answer) => answer.to_string(),
+            Err(e) => format!("error: {e:#}"),
+        };
+        return Some(Err(Mismatch {
+            part,
+            input: input_string.to_string(),
+            expected: expected.to_string(),
+            actual,
+        }));
+    }
+    compared.then_some(Ok(()))
+}
+
+/// Smaller variants of an input, roughly from the most to the least aggressive
+///
+/// We try removing blocks (separated by empty lines), chunks of lines, words (separated by spaces or commas) and one or
+/// two characters, and replacing numbers by smaller ones.
+fn shrink_candidates(input: &str) -> impl Iterator<Item = String> + '_ {
+    let blocks: Vec<_> = input.split("\n\n").collect();
+    // removing the only block would give an empty input
+    let removable_blocks = if blocks.len() > 1 { blocks.len() } else { 0 };
+    let remove_blocks =
+        (0..removable_blocks).map(move |i| [&blocks[..i], &blocks[i + 1..]].concat().join("\n\n"));
+    let lines: Vec<_> = input.lines().collect();
+    let chunk_sizes = std::iter::successors(Some(lines.len() / 2), |n| (*n > 1).then_some(n / 2));
+    let remove_lines = chunk_sizes.filter(|n| *n > 0).flat_map(move |n| {
+        let lines = lines.clone();
+        (0..lines.len()).step_by(n).map(move |i| {
+            [&lines[..i], &lines[(i + n).min(lines.len())..]]
+                .concat()
+                .join("\n")
+        })
+    });
+    let is_separator = |c: char| c == ' ' || c == ',';
+    let words = input
+        .char_indices()
+        .filter(move |(i, c)| {
+            !is_separator(*c)
+                && *c != '\n'
+                && (*i == 0 || input[..*i].ends_with(|c| is_separator(c) || c == '\n'))
+        })
+        .map(move |(start, _)| {
+            let end = input[start..]
+                .find(|c: char| is_separator(c) || c == '\n')
+                .map_or(input.len(), |len| start + len);
+            // remove the word and the separator after it, or before it if it's the last one of the line
+            match input[end..].chars().next() {
+                Some(c) if is_separator(c) => format!("{}{}", &input[..start], &input[end + 1..]),
+                _ if start > 0 && input[..start].ends_with(is_separator) => {
+                    format!("{}{}", &input[..start - 1], &input[end..])
+                }
+                _ => format!("{}{}", &input[..start], &input[end..]),
+            }
+        });
+    let numbers = input
+        .char_indices()
+        .filter(|(i, c)| c.is_ascii_digit() && !input[..*i].ends_with(|c: char| c.is_ascii_digit()))
+        .flat_map(move |(start, _)| {
+            let end = input[start..]
+                .find(|c: char| !c.is_ascii_digit())
+                .map_or(input.len(), |len| start + len);
+            let value: u64 = input[start..end].parse().unwrap_or(u64::MAX);
+            [0, 1, value / 2, value.saturating_sub(1)]
+                .into_iter()
+                .filter(move |smaller| *smaller < value)
+                .map(move |smaller| format!("{}{smaller}{}", &input[..start], &input[end..]))
+        });
+    // removing pairs of characters keeps the parity of the length (e.g. for a disk map)
+    let remove_chars = [2, 1].into_iter().flat_map(move |n| {
+        let indices: Vec<_> = input
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain([input.len()])
+            .collect();
+        (0..indices.len().saturating_sub(n))
+            .map(move |k| format!("{}{}", &input[..indices[k]], &input[indices[k + n]..]))
+    });
+    remove_blocks
+        .chain(remove_lines)
+        .chain(words)
+        .chain(numbers)
+        .chain(remove_chars)
+        .filter(move |candidate| candidate != input)
+}
+
+/// Shrink the input of a mismatch as long as the same part still disagrees with its reference
+fn shrink<D: Day>(mut mismatch: Mismatch) -> Mismatch {
+    for _ in 0..MAX_SHRINK_STEPS {
+        let smaller = shrink_candidates(&mismatch.input).find_map(|candidate| {
+            compare::<D>(&candidate)?
+                .err()
+                .filter(|m| m.part == mismatch.part)
+        });
+        match smaller {
+            Some(smaller) => mismatch = smaller,
+            None => break,
+        }
+    }
+    mismatch
+}
+
+/// Compare the implemented parts with their reference implementations on each input
+///
+/// The first mismatch is shrunk to a minimal input and returned as an error. Otherwise, the number of inputs on which
+/// at least one part could be compared is returned.
+pub fn check<D: Day>(inputs: impl IntoIterator<Item = String>) -> Result<usize> {
+    let mut compared = 0;
+    for input in inputs {
+        match compare::<D>(&input) {
+            Some(Ok(())) => compared += 1,
+            Some(Err(mismatch)) => bail!("{}", shrink::<D>(mismatch)),
+            None => {}
+        }
+    }
+    Ok(compared)
+}
+
+/// Check a [`Day`] on `cases` generated inputs of sizes up to `max_size`, and on `cases` mutations of each example
+pub fn check_day<D: Day>(cases: u64, max_size: usize, examples: &[String]) -> Result<usize> {
+    let mutations = examples.iter().flat_map(|example| {
+        [example.clone()]
+            .into_iter()
+            .chain(mutated(example, 0, cases as usize))
+    });
+    check::<D>(generated::<D>(cases, 1..=max_size.max(1)).chain(mutations))
+}
+
+/// Inputs generated with the seeds `0..cases`, with sizes cycling through `sizes`
+pub fn generated<D: Day>(cases: u64, sizes: RangeInclusive<usize>) -> impl Iterator<Item = String> {
+    let count = (sizes.end() + 1).saturating_sub(*sizes.start()).max(1) as u64;
+    (0..cases).filter_map(move |seed| D::generate(seed, sizes.start() + (seed % count) as usize))
+}
+
+/// Random variants of an input, each with a few characters replaced, removed or lines duplicated
+///
+/// Characters are replaced by another one found in the input (a digit by another digit), so that the result has a
+/// good chance of being a valid input.
+pub fn mutated(input: &str, seed: u64, cases: usize) -> impl Iterator<Item = String> + '_ {
+    let mut rng = fastrand::Rng::with_seed(seed);
+    let mut alphabet: Vec<_> = input.chars().filter(|c| *c != '\n').collect();
+    alphabet.sort_unstable();
+    alphabet.dedup();
+    (0..cases).map(move |_| {
+        let mut chars: Vec<_> = input.chars().collect();
+        for _ in 0..rng.usize(1..=3) {
+            if chars.is_empty() {
+                break;
+            }
+            let i = rng.usize(0..chars.len());
+            match rng.u8(0..4) {
+                0 if chars[i].is_ascii_digit() => chars[i] = rng.digit(10),
+                0 | 1 if chars[i] != '\n' => chars[i] = alphabet[rng.usize(0..alphabet.len())],
+                2 => {
+                    chars.remove(i);
+                }
+                _ => {
+                    // duplicate the line containing the character
+                    let start = chars[..i]
+                        .iter()
+                        .rposition(|c| *c == '\n')
+                        .map_or(0, |p| p + 1);
+                    let end = chars[i..]
+                        .iter()
+                        .position(|c| *c == '\n')
+                        .map_or(chars.len(), |p| i + p);
+                    let mut line = chars[start..end].to_vec();
+                    line.push('\n');
+                    chars.splice(start..start, line);
+                }
+            }
+        }
+        chars.into_iter().collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::fixture::Buggy;
+
+    #[test]
+    fn test_check() {
+        assert_eq!(
+            check::<Buggy>(["1 2 3".to_string(), "x".to_string()]).unwrap(),
+            1
+        );
+        let mismatch = compare::<Buggy>("3 1 42 5 17").unwrap().unwrap_err();
+        assert_eq!(mismatch.part, Part::One);
+        assert_eq!(mismatch.expected, "68");
+        assert_eq!(mismatch.actual, "9");
+        let shrunk = shrink::<Buggy>(mismatch);
+        assert_eq!(shrunk.input, "6");
+        assert_eq!(
+            check::<Buggy>(mutated("1 2 3 4", 0, 100))
+                .unwrap_err()
+                .to_string(),
+            "part 1 disagrees with its reference implementation\nexpected: 6\nactual: 0\ninput:\n6"
+        );
+    }
+
+    #[test]
+    fn test_mutated() {
+        let inputs: Vec<_> = mutated("12 34\n56", 1, 50).collect();
+        assert_eq!(inputs, mutated("12 34\n56", 1, 50).collect::<Vec<_>>());
+        assert!(inputs.iter().any(|input| input != "12 34\n56"));
+        assert!(inputs
+            .iter()
+            .all(|input| input.chars().all(|c| "0123456789 \n".contains(c))));
+    }
+}
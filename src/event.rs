@@ -0,0 +1,190 @@
+//! The schedule of the Advent of Code events
+use std::ops::RangeInclusive;
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, Datelike as _, FixedOffset, TimeDelta, TimeZone as _, Utc};
+
+/// The days of the event of `year`: 25 until 2024, then 12 from 2025 onward
+pub fn days(year: u32) -> RangeInclusive<u32> {
+    if year >= 2025 {
+        1..=12
+    } else {
+        1..=25
+    }
+}
+
+/// The time zone in which the puzzles unlock at midnight: US-Eastern, which is always UTC-5 in December
+pub fn unlock_timezone() -> FixedOffset {
+    FixedOffset::west_opt(5 * 3600).expect("the offset should be valid")
+}
+
+/// The instant at which the puzzle for `day` of `year` unlocks
+pub fn unlock_time(year: u32, day: u32) -> DateTime<Utc> {
+    unlock_timezone()
+        .with_ymd_and_hms(year as i32, 12, day, 0, 0, 0)
+        .single()
+        .expect("the days of December should be valid dates")
+        .to_utc()
+}
+
+/// The year of the current (or latest) event at `now`, which starts at the unlock of the first puzzle
+pub fn current_event(now: DateTime<Utc>) -> u32 {
+    let now = now.with_timezone(&unlock_timezone());
+    let year = if now.month() == 12 {
+        now.year()
+    } else {
+        now.year() - 1
+    };
+    year as u32
+}
+
+/// The day whose puzzle unlocked last at `now`, if an event is in progress
+///
+/// The day changes at midnight US-Eastern, whatever the local time zone.
+pub fn today(now: DateTime<Utc>) -> Option<u32> {
+    let now = now.with_timezone(&unlock_timezone());
+    (now.month() == 12 && days(now.year() as u32).contains(&now.day())).then_some(now.day())
+}
+
+/// Like [`today`], but failing outside an event, for the commands which default to the day of the puzzle
+pub fn ensure_today(now: DateTime<Utc>) -> Result<u32> {
+    match today(now) {
+        Some(day) => Ok(day),
+        None => bail!("Today is not a valid Advent of Code day. Please specify a day"),
+    }
+}
+
+/// The year and day of the next puzzle to unlock after `now`, and when it unlocks
+pub fn next_unlock(now: DateTime<Utc>) -> (u32, u32, DateTime<Utc>) {
+    let local = now.with_timezone(&unlock_timezone());
+    let year = local.year() as u32;
+    let (year, day) = match today(now) {
+        Some(day) if day < *days(year).end() => (year, day + 1),
+        Some(_) => (year + 1, 1),
+        None if local.month() == 12 => (year + 1, 1),
+        None => (year, 1),
+    };
+    (year, day, unlock_time(year, day))
+}
+
+/// Whether the puzzle for `day` of `year` is unlocked at `now`
+pub fn is_unlocked(year: u32, day: u32, now: DateTime<Utc>) -> bool {
+    now >= unlock_time(year, day)
+}
+
+/// Fail if there is no puzzle for `day` of `year`, or if it is not unlocked yet at `now`, since there is nothing to fetch
+pub fn ensure_unlocked(year: u32, day: u32, now: DateTime<Utc>) -> Result<()> {
+    let days = days(year);
+    if !days.contains(&day) {
+        bail!(
+            "the event of {year} has no day {day}, it lasts {} days",
+            days.end()
+        );
+    }
+    let unlock = unlock_time(year, day);
+    if now < unlock {
+        bail!(
+            "day {day} of {year} is not unlocked yet, it unlocks at {} (in {})",
+            unlock.format("%Y-%m-%d %H:%M UTC"),
+            human_delta(unlock - now)
+        );
+    }
+    Ok(())
+}
+
+/// A duration rounded up to the second, e.g. `3h 4m 5s`
+pub fn countdown(delta: TimeDelta) -> String {
+    let seconds = (delta.num_milliseconds().max(0) + 999) / 1000;
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    match (hours, minutes) {
+        (0, 0) => format!("{seconds}s"),
+        (0, _) => format!("{minutes}m {seconds}s"),
+        _ => format!("{hours}h {minutes}m {seconds}s"),
+    }
+}
+
+/// A duration rounded to the minute, e.g. `2d 3h 4m`
+pub fn human_delta(delta: TimeDelta) -> String {
+    let minutes = (delta.num_seconds() + 59) / 60;
+    let (days, hours, minutes) = (minutes / 1440, minutes / 60 % 24, minutes % 60);
+    match (days, hours) {
+        (0, 0) => format!("{minutes}m"),
+        (0, _) => format!("{hours}h {minutes}m"),
+        _ => format!("{days}d {hours}h {minutes}m"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unlock() {
+        let unlock = unlock_time(2024, 1);
+        assert_eq!(unlock.to_rfc3339(), "2024-12-01T05:00:00+00:00");
+        assert!(!is_unlocked(2024, 1, unlock - TimeDelta::seconds(1)));
+        assert!(is_unlocked(2024, 1, unlock));
+        assert_eq!(
+            ensure_unlocked(2024, 2, unlock).unwrap_err().to_string(),
+            "day 2 of 2024 is not unlocked yet, it unlocks at 2024-12-02 05:00 UTC (in 1d 0h 0m)"
+        );
+        assert!(ensure_unlocked(2024, 1, unlock).is_ok());
+    }
+
+    /// An instant given in US-Eastern time
+    fn eastern(month: u32, day: u32, hour: u32) -> DateTime<Utc> {
+        unlock_timezone()
+            .with_ymd_and_hms(2024, month, day, hour, 0, 0)
+            .unwrap()
+            .to_utc()
+    }
+
+    #[test]
+    fn test_today() {
+        // 11pm in New York is already the next day in UTC (and in Asia)
+        assert_eq!(today(eastern(12, 5, 23)), Some(5));
+        assert_eq!(today(eastern(12, 6, 0)), Some(6));
+        assert_eq!(today(eastern(12, 26, 1)), None);
+        assert_eq!(today(eastern(11, 30, 23)), None);
+        assert_eq!(current_event(eastern(11, 30, 23)), 2023);
+        assert_eq!(current_event(eastern(12, 1, 0)), 2024);
+    }
+
+    #[test]
+    fn test_next_unlock() {
+        assert_eq!(
+            next_unlock(eastern(11, 30, 23)),
+            (2024, 1, eastern(12, 1, 0))
+        );
+        assert_eq!(
+            next_unlock(eastern(12, 5, 23)),
+            (2024, 6, eastern(12, 6, 0))
+        );
+        assert_eq!(next_unlock(eastern(12, 25, 0)).0, 2025);
+        assert_eq!(next_unlock(eastern(12, 31, 0)).1, 1);
+    }
+
+    #[test]
+    fn test_twelve_days() {
+        assert_eq!(days(2024), 1..=25);
+        assert_eq!(days(2025), 1..=12);
+        let last = unlock_time(2025, 12);
+        assert_eq!(today(last), Some(12));
+        assert_eq!(today(unlock_time(2025, 13)), None);
+        assert_eq!(next_unlock(last), (2026, 1, unlock_time(2026, 1)));
+        assert_eq!(
+            ensure_unlocked(2025, 13, unlock_time(2026, 1))
+                .unwrap_err()
+                .to_string(),
+            "the event of 2025 has no day 13, it lasts 12 days"
+        );
+    }
+
+    #[test]
+    fn test_human_delta() {
+        assert_eq!(countdown(TimeDelta::milliseconds(3_723_400)), "1h 2m 4s");
+        assert_eq!(countdown(TimeDelta::seconds(-5)), "0s");
+        assert_eq!(human_delta(TimeDelta::seconds(1)), "1m");
+        assert_eq!(human_delta(TimeDelta::minutes(125)), "2h 5m");
+    }
+}
@@ -0,0 +1,468 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    io::{self, IsTerminal as _},
+};
+
+use winnow::{
+    combinator::{alt, delimited, preceded, terminated},
+    token::{take_till, take_until, take_while},
+    PResult, Parser as _,
+};
+
+/// A piece of an HTML document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Token<'a> {
+    /// An opening (or self-closing) tag with its name and raw attributes
+    Open { name: &'a str, attrs: &'a str },
+    /// A closing tag with its name
+    Close(&'a str),
+    /// Some text, with the HTML entities still escaped
+    Text(&'a str),
+}
+
+/// Parse a tag name
+fn parse_name<'a>(input: &mut &'a str) -> PResult<&'a str> {
+    take_while(1.., ('a'..='z', 'A'..='Z', '0'..='9', '-')).parse_next(input)
+}
+
+/// Parse a comment or a declaration (e.g. `<!DOCTYPE html>`), which are ignored
+fn parse_ignored<'a>(input: &mut &'a str) -> PResult<Option<Token<'a>>> {
+    alt((
+        delimited("<!--", take_until(0.., "-->"), "-->"),
+        delimited("<!", take_till(0.., '>'), '>'),
+    ))
+    .map(|_| None)
+    .parse_next(input)
+}
+
+/// Parse a closing tag
+fn parse_close<'a>(input: &mut &'a str) -> PResult<Option<Token<'a>>> {
+    delimited("</", parse_name, (take_till(0.., '>'), '>'))
+        .map(|name| Some(Token::Close(name)))
+        .parse_next(input)
+}
+
+/// Parse an opening tag with its attributes
+fn parse_open<'a>(input: &mut &'a str) -> PResult<Option<Token<'a>>> {
+    (
+        preceded('<', parse_name),
+        terminated(take_till(0.., '>'), '>'),
+    )
+        .map(|(name, attrs): (&str, &str)| {
+            Some(Token::Open {
+                name,
+                attrs: attrs.trim().trim_end_matches('/').trim_end(),
+            })
+        })
+        .parse_next(input)
+}
+
+/// Parse some text, or a lone `<` which doesn't start a tag
+fn parse_text<'a>(input: &mut &'a str) -> PResult<Option<Token<'a>>> {
+    alt((take_till(1.., '<'), "<"))
+        .map(|text| Some(Token::Text(text)))
+        .parse_next(input)
+}
+
+/// Split an HTML document into a list of tokens
+///
+/// This is not a general-purpose HTML parser, but it's good enough for the pages of the Advent of Code website.
+/// Comments and declarations are dropped, as well as the content of `<script>` and `<style>` elements.
+pub fn tokenize(html: &str) -> Vec<Token<'_>> {
+    let mut input = html;
+    let mut tokens = Vec::new();
+    while !input.is_empty() {
+        let Ok(token) =
+            alt((parse_ignored, parse_close, parse_open, parse_text)).parse_next(&mut input)
+        else {
+            break;
+        };
+        let Some(token) = token else {
+            continue;
+        };
+        tokens.push(token);
+        if let Token::Open {
+            name: name @ ("script" | "style"),
+            ..
+        } = token
+        {
+            // skip the raw content until the closing tag
+            let closing = format!("</{name}");
+            let end = input.find(&closing).unwrap_or(input.len());
+            input = &input[end..];
+        }
+    }
+    tokens
+}
+
+/// Replace the HTML entities in `text` with the characters they represent
+pub fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';').filter(|end| *end <= 10) else {
+            out.push('&');
+            rest = &rest[1..];
+            continue;
+        };
+        let decoded = match &rest[1..end] {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some('\u{a0}'),
+            entity => entity
+                .strip_prefix("#x")
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Extract the content of the `<pre><code>` blocks of a puzzle page, in order of appearance
+///
+/// Any formatting tag inside of the blocks (e.g. `<em>`) is removed.
+pub fn code_blocks(html: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut current: Option<String> = None;
+    let mut prev = None;
+    for token in tokenize(html) {
+        match (token, prev, current.as_mut()) {
+            (Token::Open { name: "code", .. }, Some(Token::Open { name: "pre", .. }), _) => {
+                current = Some(String::new());
+            }
+            (Token::Close("code"), _, Some(_)) => {
+                blocks.extend(current.take());
+            }
+            (Token::Text(text), _, Some(block)) => {
+                block.push_str(&unescape(text));
+            }
+            _ => {}
+        }
+        prev = Some(token);
+    }
+    blocks
+}
+
+/// The name of the logged in user, as shown in the header of the pages (e.g. `(anonymous user #123456)`)
+///
+/// Returns `None` if nobody is logged in.
+pub fn user_name(html: &str) -> Option<String> {
+    let tokens = tokenize(html);
+    let start = tokens.iter().position(
+        |token| matches!(token, Token::Open { name: "div", attrs } if *attrs == "class=\"user\""),
+    )?;
+    match tokens.get(start + 1)? {
+        Token::Text(text) if !text.trim().is_empty() => Some(unescape(text.trim())),
+        _ => None,
+    }
+}
+
+/// How the puzzle text is rendered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// Markdown-like markers, e.g. `*emphasis*` and `` `code` ``
+    Plain,
+    /// ANSI escape codes: bold emphasis, colored code and underlined links
+    Ansi,
+}
+
+impl Style {
+    /// ANSI escape codes when the standard output is a terminal, unless `NO_COLOR` is set
+    pub fn for_stdout() -> Self {
+        if io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none() {
+            Style::Ansi
+        } else {
+            Style::Plain
+        }
+    }
+
+    /// The markers around an element, or `None` if it's not an inline element
+    ///
+    /// In plain text, the emphasis within code is dropped since it can't be told apart from the code itself.
+    fn markers(self, name: &str, in_code: bool) -> Option<(&'static str, &'static str)> {
+        let markers = match (self, name) {
+            (Style::Plain, "em") if in_code => ("", ""),
+            (Style::Plain, "em") => ("*", "*"),
+            (Style::Plain, "code") if in_code => ("", ""),
+            (Style::Plain, "code") => ("`", "`"),
+            (Style::Plain, "a" | "h2") => ("", ""),
+            (Style::Ansi, "em" | "h2") => ("\x1b[1m", "\x1b[22m"),
+            (Style::Ansi, "code") => ("\x1b[36m", "\x1b[39m"),
+            (Style::Ansi, "a") => ("\x1b[4m", "\x1b[24m"),
+            _ => return None,
+        };
+        Some(markers)
+    }
+}
+
+/// The value of the attribute `name` in the raw attributes of a tag
+fn attribute<'a>(attrs: &'a str, name: &str) -> Option<&'a str> {
+    let start = attrs.find(&format!("{name}=\""))? + name.len() + 2;
+    let len = attrs[start..].find('"')?;
+    Some(&attrs[start..start + len])
+}
+
+/// End the current block of text with an empty line, unless nothing was written yet
+fn block_break(out: &mut String) {
+    while out.ends_with(' ') || out.ends_with('\n') {
+        out.pop();
+    }
+    if !out.is_empty() {
+        out.push_str("\n\n");
+    }
+}
+
+/// Render the `<article>` elements of a puzzle page (one per part which is unlocked) as terminal text
+///
+/// Paragraphs are separated by empty lines, code blocks are indented by four spaces, list items start with `- ` and
+/// links are followed by their target in parentheses. Whitespace is collapsed outside of code.
+pub fn render_articles(html: &str, style: Style) -> Vec<String> {
+    let mut articles = Vec::new();
+    let mut current: Option<String> = None;
+    // depth of the lists and of the code elements, whether we are in a code block, and the target of the current link
+    let (mut lists, mut code, mut pre, mut link) = (0, 0, false, None);
+    for token in tokenize(html) {
+        let Some(out) = current.as_mut() else {
+            if matches!(
+                token,
+                Token::Open {
+                    name: "article",
+                    ..
+                }
+            ) {
+                current = Some(String::new());
+            }
+            continue;
+        };
+        match token {
+            Token::Close("article") => {
+                articles.extend(current.take().map(|article| article.trim_end().to_string()));
+            }
+            Token::Open { name: "pre", .. } => {
+                block_break(out);
+                out.push_str("    ");
+                pre = true;
+            }
+            Token::Close("pre") => {
+                block_break(out);
+                pre = false;
+            }
+            Token::Open { name: "p", .. } | Token::Close("p") => block_break(out),
+            Token::Open {
+                name: "ul" | "ol", ..
+            } => {
+                if lists == 0 {
+                    block_break(out);
+                }
+                lists += 1;
+            }
+            Token::Close("ul" | "ol") => {
+                lists -= 1;
+                if lists == 0 {
+                    block_break(out);
+                }
+            }
+            Token::Open { name: "li", .. } => {
+                let len = out.trim_end_matches(' ').len();
+                out.truncate(len);
+                if !out.is_empty() && !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str(&"  ".repeat(lists.max(1) - 1));
+                out.push_str("- ");
+            }
+            Token::Open { name: "br", .. } => out.push('\n'),
+            Token::Open { name, attrs } => {
+                if name == "h2" {
+                    block_break(out);
+                }
+                if let Some((open, _)) = style.markers(name, code > 0 || pre) {
+                    out.push_str(open);
+                }
+                match name {
+                    "code" => code += 1,
+                    "a" => link = attribute(attrs, "href"),
+                    _ => {}
+                }
+            }
+            Token::Close(name) => {
+                if name == "code" {
+                    code -= 1;
+                }
+                if let Some((_, close)) = style.markers(name, code > 0 || pre) {
+                    out.push_str(close);
+                }
+                match (name, link.take()) {
+                    ("a", Some(href)) => out.push_str(&format!(" ({})", unescape(href))),
+                    ("h2", _) => block_break(out),
+                    (_, href) => link = href,
+                }
+            }
+            Token::Text(text) if pre => out.push_str(&unescape(text).replace('\n', "\n    ")),
+            Token::Text(text) if code > 0 => out.push_str(&unescape(text).replace('\n', " ")),
+            Token::Text(text) => {
+                let text = unescape(text);
+                let mut collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                if text.starts_with(char::is_whitespace)
+                    && !out.is_empty()
+                    && !out.ends_with([' ', '\n'])
+                {
+                    collapsed.insert(0, ' ');
+                }
+                if text.ends_with(char::is_whitespace) && !collapsed.trim().is_empty() {
+                    collapsed.push(' ');
+                }
+                out.push_str(&collapsed);
+            }
+        }
+    }
+    articles
+}
+
+/// The number of stars obtained on each unlocked day, as shown on the calendar page of an event
+///
+/// The days of the calendar are links whose class is `calendar-dayN`, followed by `calendar-complete` with one star or
+/// `calendar-verycomplete` with two. Days which are not unlocked yet are missing.
+pub fn calendar_stars(html: &str) -> BTreeMap<u32, u8> {
+    tokenize(html)
+        .into_iter()
+        .filter_map(|token| match token {
+            Token::Open { name: "a", attrs } => attribute(attrs, "class"),
+            _ => None,
+        })
+        .filter_map(|class| {
+            let mut classes = class.split_whitespace();
+            let day = classes.next()?.strip_prefix("calendar-day")?.parse().ok()?;
+            let stars = match classes.next() {
+                Some("calendar-verycomplete") => 2,
+                Some("calendar-complete") => 1,
+                _ => 0,
+            };
+            Some((day, stars))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = include_str!("../tests/fixtures/day01.html");
+
+    #[test]
+    fn test_tokenize() {
+        let tokens = tokenize("<!-- hi --><p class=\"x\">a <em>b</em><br/>&lt;</p>");
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Open {
+                    name: "p",
+                    attrs: "class=\"x\""
+                },
+                Token::Text("a "),
+                Token::Open {
+                    name: "em",
+                    attrs: ""
+                },
+                Token::Text("b"),
+                Token::Close("em"),
+                Token::Open {
+                    name: "br",
+                    attrs: ""
+                },
+                Token::Text("&lt;"),
+                Token::Close("p"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unescape() {
+        assert_eq!(
+            unescape("a &lt; b &amp;&amp; c&#62;d &#x41; & e"),
+            "a < b && c>d A & e"
+        );
+    }
+
+    #[test]
+    fn test_code_blocks() {
+        let blocks = code_blocks(PAGE);
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0], "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n");
+        assert_eq!(blocks[1], blocks[0]);
+        assert_eq!(blocks[2], "a < b && c > d\n");
+    }
+
+    #[test]
+    fn test_render_articles() {
+        let articles = render_articles(PAGE, Style::Plain);
+        assert_eq!(articles.len(), 2);
+        assert!(articles[0].starts_with(
+            "--- Day 1: Historian Hysteria ---\n\nThe *Chief Historian* is always present"
+        ));
+        assert!(articles[0].contains("For example:\n\n    3   4\n    4   3\n"));
+        assert!(articles[0].contains("    3   3\n\nMaybe the lists"));
+        assert!(articles[0].contains(
+            "measure how far apart they are.\n\n- The smallest number in the left list is `1`, and"
+        ));
+        assert!(articles[0].contains("The distance between them is `2`.\n- The second-smallest"));
+        assert!(articles[0].ends_with("*What is the total distance between your lists?*"));
+        assert!(!articles[0].contains("2378066"));
+        assert!(articles[1].starts_with("--- Part Two ---\n\n"));
+        assert!(articles[1].contains("    3   9\n"));
+        assert!(articles[1].contains("    a < b && c > d\n\nOnce again"));
+    }
+
+    #[test]
+    fn test_render_inline() {
+        let html = r#"<article><p>Go <a href="/2024/day/1#part2">there</a>, <em>now <code>x</code></em>.</p>
+<ul><li>a<ul><li>b</li></ul></li><li>c</li></ul></article>"#;
+        assert_eq!(
+            render_articles(html, Style::Plain),
+            vec!["Go there (/2024/day/1#part2), *now `x`*.\n\n- a\n  - b\n- c"]
+        );
+        assert_eq!(
+            render_articles(
+                "<article><p><em>a</em> <code>b</code></p></article>",
+                Style::Ansi
+            ),
+            vec!["\x1b[1ma\x1b[22m \x1b[36mb\x1b[39m"]
+        );
+    }
+
+    #[test]
+    fn test_calendar_stars() {
+        let stars = calendar_stars(include_str!("../tests/fixtures/calendar.html"));
+        assert_eq!(stars, BTreeMap::from([(1, 2), (2, 1), (3, 0)]));
+        assert!(calendar_stars(PAGE).is_empty());
+    }
+
+    #[test]
+    fn test_user_name() {
+        let header = r#"<header><div><h1 class="title-global"><a href="/">Advent of Code</a></h1><nav></nav><div class="user">(anonymous user #123456) <a href="/2024/support" class="supporter-badge">AoC++</a> <span class="star-count">50*</span></div></div></header>"#;
+        assert_eq!(
+            user_name(header).as_deref(),
+            Some("(anonymous user #123456)")
+        );
+        assert_eq!(user_name(PAGE).as_deref(), Some("Historian"));
+        assert_eq!(user_name("<a href=\"/auth/login\">[Log In]</a>"), None);
+    }
+}
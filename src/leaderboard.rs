@@ -0,0 +1,327 @@
+//! Private leaderboards, as served in JSON by the website
+//!
+//! The website asks not to request a leaderboard more than once every 15 minutes, so the JSON is cached and only
+//! downloaded again once the cached copy is older than [`MIN_REFRESH_INTERVAL`].
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write as _,
+    fs,
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{Context as _, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::{client::Client, event::unlock_time, profile::Profile};
+
+/// The minimum time between two downloads of the same leaderboard
+pub const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// The location of the cached JSON of the private leaderboard `id` for `year`
+pub fn leaderboard_path(year: u32, id: u64) -> String {
+    format!("inputs/leaderboards/{year}/{id}.json")
+}
+
+/// The contents of the cached file at `path`, if it was written less than `max_age` ago
+pub fn cached(path: impl AsRef<Path>, max_age: Duration) -> Option<String> {
+    let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+    let age = SystemTime::now()
+        .duration_since(modified)
+        .unwrap_or_default();
+    if age >= max_age {
+        return None;
+    }
+    fs::read_to_string(path).ok()
+}
+
+/// The leaderboard cached at `path` if it is recent enough, otherwise the one returned by `download`
+///
+/// A downloaded leaderboard is only cached once it parses, since the website may answer with something else (e.g. the
+/// login page when the session expired). If the download fails in either way, the stale cache is used with a warning.
+pub fn load_leaderboard(
+    path: impl AsRef<Path>,
+    download: impl FnOnce() -> Result<String>,
+) -> Result<Leaderboard> {
+    let path = path.as_ref();
+    if let Some(json) = cached(path, MIN_REFRESH_INTERVAL) {
+        return Leaderboard::parse(&json);
+    }
+    let downloaded = download().and_then(|json| Ok((Leaderboard::parse(&json)?, json)));
+    match downloaded {
+        Ok((leaderboard, json)) => {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir).context("creating the leaderboards directory")?;
+            }
+            fs::write(path, json).context("writing leaderboard to file")?;
+            Ok(leaderboard)
+        }
+        Err(e) => match fs::read_to_string(path) {
+            Ok(json) => {
+                eprintln!(
+                    "Could not download the leaderboard again ({e:#}), using {}",
+                    path.display()
+                );
+                Leaderboard::parse(&json)
+            }
+            Err(_) => Err(e),
+        },
+    }
+}
+
+/// Print the private leaderboard `id` of `year` (or only `day` of it), see [`load_leaderboard`]
+pub fn print_leaderboard(
+    base_url: &str,
+    profile: &Profile,
+    year: u32,
+    id: u64,
+    day: Option<u32>,
+) -> Result<()> {
+    let leaderboard = load_leaderboard(leaderboard_path(year, id), || {
+        Client::new(base_url, profile)?.leaderboard(year, id)
+    })?;
+    print!("{}", leaderboard.render(day)?);
+    Ok(())
+}
+
+/// The time at which a star was obtained
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct Star {
+    #[serde(rename = "get_star_ts")]
+    pub timestamp: i64,
+}
+
+/// A member of a private leaderboard
+#[derive(Debug, Clone, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// Anonymous users have no name
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u64,
+    /// The stars obtained by day, then by part
+    #[serde(rename = "completion_day_level")]
+    pub days: BTreeMap<u32, BTreeMap<u32, Star>>,
+}
+
+impl Member {
+    /// The name of the member as shown by the website
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    /// The time at which the member got the star for `part` of `day`
+    pub fn star(&self, day: u32, part: u32) -> Option<DateTime<Utc>> {
+        let star = self.days.get(&day)?.get(&part)?;
+        DateTime::from_timestamp(star.timestamp, 0)
+    }
+}
+
+/// A private leaderboard of an event
+#[derive(Debug, Clone, Deserialize)]
+pub struct Leaderboard {
+    #[serde(rename = "event")]
+    pub year: String,
+    pub members: BTreeMap<String, Member>,
+}
+
+impl Leaderboard {
+    /// Parse the JSON of a leaderboard
+    pub fn parse(json: &str) -> Result<Self> {
+        serde_json::from_str(json).context("parsing the leaderboard")
+    }
+
+    /// The members by rank: highest local score first, then most stars, then earliest star
+    pub fn standings(&self) -> Vec<&Member> {
+        let mut members: Vec<_> = self.members.values().collect();
+        members.sort_by_key(|m| {
+            let last_star = m
+                .days
+                .values()
+                .flat_map(|parts| parts.values())
+                .map(|s| s.timestamp)
+                .max();
+            (Reverse(m.local_score), Reverse(m.stars), last_star, m.id)
+        });
+        members
+    }
+
+    /// Render the standings, then the times of each member for each day (or only for `day`) since the puzzle
+    /// unlocked
+    pub fn render(&self, day: Option<u32>) -> Result<String> {
+        let year: u32 = self
+            .year
+            .parse()
+            .context("parsing the year of the leaderboard")?;
+        let standings = self.standings();
+        let width = standings
+            .iter()
+            .map(|m| m.display_name().chars().count())
+            .max()
+            .unwrap_or_default()
+            .max(6);
+        let mut out = String::new();
+        writeln!(
+            out,
+            "Private leaderboard {year} ({} members)",
+            standings.len()
+        )?;
+        writeln!(out)?;
+        for (rank, member) in standings.iter().enumerate() {
+            writeln!(
+                out,
+                "{:>3}) {:>5} {:>3}* {}",
+                rank + 1,
+                member.local_score,
+                member.stars,
+                member.display_name()
+            )?;
+        }
+        let days = self
+            .members
+            .values()
+            .flat_map(|m| m.days.keys().copied())
+            .filter(|d| day.is_none_or(|day| day == *d))
+            .collect::<BTreeSet<_>>();
+        for day in days {
+            let unlock = unlock_time(year, day);
+            let mut times: Vec<_> = standings
+                .iter()
+                .filter_map(|m| {
+                    let part_1 = m.star(day, 1)?;
+                    Some((m, part_1, m.star(day, 2)))
+                })
+                .collect();
+            // like on the website, the fastest to get both stars come first
+            times.sort_by_key(|(_, part_1, part_2)| (part_2.is_none(), *part_2, *part_1));
+            writeln!(out)?;
+            writeln!(
+                out,
+                "{:<width$} {:>11} {:>11} {:>11}",
+                format!("Day {day}"),
+                "Part 1",
+                "Part 2",
+                "Delta"
+            )?;
+            for (member, part_1, part_2) in times {
+                let (part_2, delta) = match part_2 {
+                    Some(part_2) => (
+                        elapsed((part_2 - unlock).num_seconds()),
+                        elapsed((part_2 - part_1).num_seconds()),
+                    ),
+                    None => ("-".to_string(), "-".to_string()),
+                };
+                writeln!(
+                    out,
+                    "{:<width$} {:>11} {part_2:>11} {delta:>11}",
+                    member.display_name(),
+                    elapsed((part_1 - unlock).num_seconds()),
+                )?;
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// A number of seconds as `HH:MM:SS`, preceded by the number of days if there are any (e.g. `1d 02:03:04`)
+fn elapsed(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    let (days, hours, minutes, seconds) = (
+        seconds / 86400,
+        seconds / 3600 % 24,
+        seconds / 60 % 60,
+        seconds % 60,
+    );
+    if days > 0 {
+        format!("{days}d {hours:02}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{hours:02}:{minutes:02}:{seconds:02}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &str = include_str!("../tests/fixtures/leaderboard.json");
+
+    #[test]
+    fn test_load_leaderboard() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("2024").join("1.json");
+        // nothing to fall back to
+        assert!(load_leaderboard(&path, || Ok("<html>".to_string())).is_err());
+        assert!(!path.exists());
+        let leaderboard = load_leaderboard(&path, || Ok(JSON.to_string())).unwrap();
+        assert_eq!(leaderboard.members.len(), 3);
+        assert_eq!(fs::read_to_string(&path).unwrap(), JSON);
+        // the cache is fresh, so nothing is downloaded
+        assert!(load_leaderboard(&path, || unreachable!()).is_ok());
+        // once it is stale, a page which isn't a leaderboard is not cached
+        let stale = SystemTime::now() - MIN_REFRESH_INTERVAL;
+        fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(stale)
+            .unwrap();
+        let leaderboard = load_leaderboard(&path, || Ok("<html>".to_string())).unwrap();
+        assert_eq!(leaderboard.members.len(), 3);
+        assert_eq!(fs::read_to_string(&path).unwrap(), JSON);
+    }
+
+    #[test]
+    fn test_standings() {
+        let leaderboard = Leaderboard::parse(JSON).unwrap();
+        let names = leaderboard
+            .standings()
+            .iter()
+            .map(|m| m.display_name())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["Alice", "(anonymous user #2)", "Bob"]);
+        assert!(Leaderboard::parse("{\"members\": []}").is_err());
+    }
+
+    #[test]
+    fn test_render() {
+        let leaderboard = Leaderboard::parse(JSON).unwrap();
+        assert_eq!(
+            leaderboard.render(None).unwrap(),
+            "Private leaderboard 2024 (3 members)
+
+  1)     5   3* Alice
+  2)     3   2* (anonymous user #2)
+  3)     0   0* Bob
+
+Day 1                    Part 1      Part 2       Delta
+Alice                  00:05:00    00:07:00    00:02:00
+(anonymous user #2)    00:03:20    00:15:00    00:11:40
+
+Day 2                    Part 1      Part 2       Delta
+Alice               1d 00:00:03           -           -
+"
+        );
+        assert!(!leaderboard.render(Some(2)).unwrap().contains("Day 1 "));
+    }
+
+    #[test]
+    fn test_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("1.json");
+        assert_eq!(cached(&path, MIN_REFRESH_INTERVAL), None);
+        fs::write(&path, JSON).unwrap();
+        assert_eq!(cached(&path, MIN_REFRESH_INTERVAL).as_deref(), Some(JSON));
+        assert_eq!(cached(&path, Duration::ZERO), None);
+    }
+
+    #[test]
+    fn test_elapsed() {
+        assert_eq!(elapsed(3723), "01:02:03");
+        assert_eq!(elapsed(90061), "1d 01:01:01");
+    }
+}
@@ -0,0 +1,35 @@
+//! Solutions to the Advent of Code puzzles, and the tools to run, check, benchmark and submit them
+//!
+//! Each puzzle is implemented as a [`Day`], and the implementations are registered per year in [`YEARS`]. The CLI in
+//! `main.rs` is a thin layer over this library, which can be used to run the solutions from other tools:
+//!
+//! ```no_run
+//! use aoc_2024::{get_solution, input_path};
+//!
+//! let report = get_solution(2024, 1)?.run(input_path(2024, 1), &[]);
+//! println!("{report}");
+//! # anyhow::Ok(())
+//! ```
+pub mod alloc;
+pub mod answer;
+pub mod answers;
+pub mod bench;
+pub mod client;
+pub mod days;
+pub mod diagnostics;
+pub mod differential;
+pub mod event;
+pub mod html;
+pub mod leaderboard;
+pub mod profile;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod status;
+pub mod submit;
+
+pub use answer::Answer;
+pub use days::{
+    all_solutions, example_path, get_solution, input_path, latest_year, puzzle_path, read_examples,
+    read_input, solutions, Day, Param, Params, Part, Solution, YEARS,
+};
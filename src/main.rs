@@ -1,18 +1,33 @@
-use std::{fs, ops::RangeInclusive};
+use std::ops::{RangeFrom, RangeInclusive};
 
-use anyhow::{bail, Context as _, Result};
-use chrono::{Datelike, Local};
+use anyhow::{Context as _, Result};
+use chrono::Utc;
 use clap::{Parser, Subcommand};
 
-#[allow(clippy::wildcard_imports)]
-use days::*;
+use aoc_2024::{
+    all_solutions,
+    alloc::{self, CountingAllocator},
+    bench::{bench_days, bench_generated, BenchOptions, BASELINE_FILE},
+    client::{get_all, get_day, read_puzzle, wait_for_input, Client, DEFAULT_BASE_URL},
+    event, example_path, get_solution,
+    html::Style,
+    leaderboard::print_leaderboard,
+    profile::Profile,
+    read_examples,
+    report::OutputFormat,
+    runner::{run_days, verify_days},
+    scaffold::new_day,
+    solutions,
+    status::print_status,
+    submit::submit_answer,
+    Param,
+};
 
-mod days;
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
 
-const YEAR: usize = 2024; // change this if needed
+const CLI_YEAR_RANGE: RangeFrom<i64> = 2015..;
 const CLI_DAY_RANGE: RangeInclusive<i64> = 1..=25;
-const VALID_DAY_RANGE: RangeInclusive<u32> =
-    (*CLI_DAY_RANGE.start() as u32)..=(*CLI_DAY_RANGE.end() as u32);
 
 /// Advent of Code
 #[derive(Parser)]
@@ -21,6 +36,22 @@ const VALID_DAY_RANGE: RangeInclusive<u32> =
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    #[arg(
+        long,
+        global = true,
+        env = "AOC_BASE_URL",
+        default_value = DEFAULT_BASE_URL,
+        help = "The base URL of the Advent of Code website"
+    )]
+    base_url: String,
+    #[arg(
+        short = 'P',
+        long,
+        global = true,
+        env = "AOC_PROFILE",
+        help = "The profile (account) whose session, inputs and answers are used, see the config file"
+    )]
+    profile: Option<String>,
 }
 
 #[derive(Subcommand)]
@@ -32,8 +63,57 @@ enum Commands {
             help = "The number of the day you want to run (1-25)")
         ]
         day: Option<u32>,
-        #[arg(short, long, help = "Runs all days sequentially")]
+        #[arg(short, long, help = "Runs all days")]
         all: bool,
+        #[arg(
+            short,
+            long,
+            conflicts_with_all = ["all", "example"],
+            help = "Reads the input from this file instead (`-` for the standard input)"
+        )]
+        input: Option<String>,
+        #[arg(
+            short,
+            long,
+            conflicts_with = "all",
+            help = "Runs with the K-th example of the day instead (starting at 1)"
+        )]
+        example: Option<usize>,
+        #[arg(
+            short,
+            long = "param",
+            value_name = "NAME=VALUE",
+            conflicts_with = "all",
+            help = "Overrides a runtime parameter of the puzzle (e.g. the size of the grid)"
+        )]
+        params: Vec<Param>,
+        #[arg(
+            short, long,
+            value_parser = clap::value_parser!(u32).range(CLI_YEAR_RANGE),
+            help = "The year of the event (defaults to the latest year with solutions)")
+        ]
+        year: Option<u32>,
+        #[arg(short, long, value_enum, default_value_t, help = "The output format")]
+        format: OutputFormat,
+        #[arg(
+            short,
+            long,
+            help = "Number of days to run concurrently (defaults to the number of CPUs)"
+        )]
+        jobs: Option<usize>,
+        #[arg(
+            long,
+            conflicts_with = "jobs",
+            help = "Reports the allocations, bytes allocated and peak memory of each phase (runs the days one at a time)"
+        )]
+        profile_alloc: bool,
+        #[arg(
+            short,
+            long,
+            conflicts_with_all = ["day", "all", "input", "example", "year"],
+            help = "Waits for the next puzzle to unlock, downloads its input and runs it"
+        )]
+        wait: bool,
     },
     /// Get the input file for one or all days
     Get {
@@ -44,111 +124,496 @@ enum Commands {
         day: Option<u32>,
         #[arg(short, long, help = "Downloads input for all days sequentially")]
         all: bool,
+        #[arg(
+            short, long,
+            value_parser = clap::value_parser!(u32).range(CLI_YEAR_RANGE),
+            help = "The year of the event (defaults to the current event)")
+        ]
+        year: Option<u32>,
+        #[arg(
+            short,
+            long,
+            help = "Extracts the examples from the puzzle page instead of downloading the input"
+        )]
+        examples: bool,
+        #[arg(
+            short,
+            long,
+            help = "Downloads the files again even if they already exist"
+        )]
+        force: bool,
+        #[arg(
+            short,
+            long,
+            conflicts_with_all = ["day", "all", "year", "examples"],
+            help = "Waits for the next puzzle to unlock and downloads its input"
+        )]
+        wait: bool,
+    },
+    /// Print the text of a puzzle, from the cached page if possible
+    Read {
+        #[arg(
+            value_parser = clap::value_parser!(u32).range(CLI_DAY_RANGE),
+            help = "The number of the day you want to read (1-25)")
+        ]
+        day: u32,
+        #[arg(
+            short, long,
+            value_parser = clap::value_parser!(u32).range(CLI_YEAR_RANGE),
+            help = "The year of the event (defaults to the current event)")
+        ]
+        year: Option<u32>,
+        #[arg(
+            short,
+            long,
+            help = "Downloads the puzzle page again even if it is cached"
+        )]
+        refresh: bool,
+    },
+    /// Show which days are implemented, downloaded, run and solved
+    Status {
+        #[arg(
+            short, long,
+            value_parser = clap::value_parser!(u32).range(CLI_YEAR_RANGE),
+            help = "The year of the event (defaults to the latest year with solutions)")
+        ]
+        year: Option<u32>,
+        #[arg(
+            short,
+            long,
+            help = "Also shows the stars of the account, from the calendar page of the event"
+        )]
+        stars: bool,
+        #[arg(
+            short,
+            long,
+            help = "Also runs the unit tests of the solutions with cargo test and shows their results"
+        )]
+        tests: bool,
+    },
+    /// Show the standings and times of a private leaderboard
+    Leaderboard {
+        #[arg(help = "The ID of the private leaderboard (the number in its URL)")]
+        id: u64,
+        #[arg(
+            short, long,
+            value_parser = clap::value_parser!(u32).range(CLI_YEAR_RANGE),
+            help = "The year of the event (defaults to the current event)")
+        ]
+        year: Option<u32>,
+        #[arg(
+            short,
+            long,
+            value_parser = clap::value_parser!(u32).range(CLI_DAY_RANGE),
+            help = "Only shows the times for this day"
+        )]
+        day: Option<u32>,
+    },
+    /// Check that the session cookie is valid and print the name of its account
+    Whoami {
+        #[arg(
+            long,
+            default_value = "/settings",
+            help = "The page of the website (relative to the base URL) which shows the logged in user"
+        )]
+        endpoint: String,
+    },
+    /// Compute the answer for a part of a day and submit it
+    Submit {
+        #[arg(
+            value_parser = clap::value_parser!(u32).range(CLI_DAY_RANGE),
+            help = "The number of the day you want to submit an answer for (1-25)")
+        ]
+        day: u32,
+        #[arg(
+            value_parser = clap::value_parser!(u8).range(1..=2),
+            help = "The part of the puzzle you want to submit an answer for (1-2)")
+        ]
+        part: u8,
+        #[arg(
+            short, long,
+            value_parser = clap::value_parser!(u32).range(CLI_YEAR_RANGE),
+            help = "The year of the event (defaults to the current event)")
+        ]
+        year: Option<u32>,
+    },
+    /// Check the answers of one or all days against the accepted answers
+    Verify {
+        #[arg(
+            value_parser = clap::value_parser!(u32).range(CLI_DAY_RANGE),
+            help = "The number of the day you want to verify (1-25)")
+        ]
+        day: Option<u32>,
+        #[arg(short, long, help = "Verifies all days sequentially")]
+        all: bool,
+        #[arg(
+            short, long,
+            value_parser = clap::value_parser!(u32).range(CLI_YEAR_RANGE),
+            help = "The year of the event (defaults to the latest year with solutions)")
+        ]
+        year: Option<u32>,
+    },
+    /// Benchmark the parser, the preparation step and both parts of one or all days
+    Bench {
+        #[arg(
+            value_parser = clap::value_parser!(u32).range(CLI_DAY_RANGE),
+            help = "The number of the day you want to benchmark (1-25)")
+        ]
+        day: Option<u32>,
+        #[arg(short, long, help = "Benchmarks all days sequentially")]
+        all: bool,
+        #[arg(
+            short, long,
+            value_parser = clap::value_parser!(u32).range(CLI_YEAR_RANGE),
+            help = "The year of the event (defaults to the latest year with solutions)")
+        ]
+        year: Option<u32>,
+        #[arg(
+            long,
+            default_value_t = 3,
+            help = "Number of unmeasured runs of each phase"
+        )]
+        warmup: usize,
+        #[arg(
+            short,
+            long,
+            default_value_t = 20,
+            help = "Number of measured runs of each phase"
+        )]
+        iterations: usize,
+        #[arg(
+            long,
+            default_value = BASELINE_FILE,
+            help = "The baseline file to compare against")
+        ]
+        baseline: String,
+        #[arg(short, long, help = "Save the results to the baseline file")]
+        save: bool,
+        #[arg(
+            short,
+            long,
+            default_value_t = 10.0,
+            help = "Median slowdown (in percent) above which a regression is reported"
+        )]
+        threshold: f64,
+        #[arg(
+            long,
+            conflicts_with_all = ["all", "save"],
+            help = "Benchmark against a generated input of this size instead of the input file (see `gen`)"
+        )]
+        size: Option<usize>,
+        #[arg(
+            long,
+            default_value_t = 0,
+            requires = "size",
+            help = "The seed of the generated input"
+        )]
+        seed: u64,
+    },
+    /// Print a random input for a day, for stress-testing and benchmarking
+    Gen {
+        #[arg(
+            value_parser = clap::value_parser!(u32).range(CLI_DAY_RANGE),
+            help = "The number of the day you want an input for (1-25)")
+        ]
+        day: u32,
+        #[arg(
+            short, long,
+            value_parser = clap::value_parser!(u32).range(CLI_YEAR_RANGE),
+            help = "The year of the event (defaults to the latest year with solutions)")
+        ]
+        year: Option<u32>,
+        #[arg(
+            long,
+            default_value_t = 0,
+            help = "The seed of the random generator, the same seed always gives the same input"
+        )]
+        seed: u64,
+        #[arg(
+            long,
+            help = "The size of the input, whose meaning depends on the day (e.g. the width of the grid)"
+        )]
+        size: usize,
+    },
+    /// Compare the parts of a day with their reference implementations on random inputs
+    Check {
+        #[arg(
+            value_parser = clap::value_parser!(u32).range(CLI_DAY_RANGE),
+            help = "The number of the day you want to check (1-25)")
+        ]
+        day: u32,
+        #[arg(
+            short, long,
+            value_parser = clap::value_parser!(u32).range(CLI_YEAR_RANGE),
+            help = "The year of the event (defaults to the latest year with solutions)")
+        ]
+        year: Option<u32>,
+        #[arg(
+            short,
+            long,
+            default_value_t = 100,
+            help = "Number of generated inputs, and of mutations of each example"
+        )]
+        cases: u64,
+        #[arg(
+            long,
+            default_value_t = 10,
+            help = "The maximum size of the generated inputs (see `gen`)"
+        )]
+        max_size: usize,
+    },
+    /// Generate and register the solution module for a day
+    New {
+        #[arg(
+            value_parser = clap::value_parser!(u32).range(CLI_DAY_RANGE),
+            help = "The number of the day you want to start (1-25)")
+        ]
+        day: u32,
+        #[arg(
+            short, long,
+            value_parser = clap::value_parser!(u32).range(CLI_YEAR_RANGE),
+            help = "The year of the event (defaults to the current event)")
+        ]
+        year: Option<u32>,
+    },
+    /// List the registered solutions
+    List {
+        #[arg(
+            short, long,
+            value_parser = clap::value_parser!(u32).range(CLI_YEAR_RANGE),
+            help = "Only list the solutions for this year")
+        ]
+        year: Option<u32>,
     },
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    let profile = Profile::new(cli.profile.as_deref())?;
 
     match cli.command {
-        Commands::Run { day, all } => {
-            if all {
-                return run_all_days();
+        Commands::Run {
+            day,
+            all,
+            input,
+            example,
+            params,
+            year,
+            format,
+            jobs,
+            profile_alloc,
+            wait,
+        } => {
+            let year = year.unwrap_or_else(latest_year);
+            // the allocation counters are shared by all threads, so the days must not overlap
+            let jobs = if profile_alloc {
+                alloc::enable()?;
+                Some(1)
+            } else {
+                jobs
+            };
+            // only the runs on the day's own input are recorded for the status
+            let last_runs = profile.last_runs_file();
+            let record = params.is_empty().then_some(last_runs.as_str());
+            if wait {
+                let client = Client::new(&cli.base_url, &profile)?;
+                let (year, day) = wait_for_input(&client, &profile, false)?;
+                let path = profile.input_path(year, day);
+                let solution = get_solution(year, day).with_context(|| {
+                    format!("the input was saved to {path}, but there is nothing to run yet")
+                })?;
+                return run_days([(solution, path)], &params, format, jobs, record);
             }
-            if let Some(day) = day {
-                return run_day(day);
+            if all {
+                let days = solutions(year).map(|s| (s, profile.input_path(year, s.day)));
+                return run_days(days, &[], format, jobs, record);
             }
-            println!("No day parameter specified, attempting to run today's code");
-            let now_day = get_today()?;
-            println!("Running day {now_day}");
-            run_day(now_day)
+            let day = match day {
+                Some(day) => day,
+                None => {
+                    eprintln!("No day parameter specified, attempting to run today's code");
+                    let now_day = event::ensure_today(Utc::now())?;
+                    eprintln!("Running day {now_day}");
+                    now_day
+                }
+            };
+            let (path, record) = match (input, example) {
+                (Some(path), _) => (path, None),
+                (None, Some(example)) => (example_path(year, day, example), None),
+                (None, None) => (profile.input_path(year, day), record),
+            };
+            run_days(
+                [(get_solution(year, day)?, path)],
+                &params,
+                format,
+                jobs,
+                record,
+            )
         }
-        Commands::Get { day, all } => {
+        Commands::Get {
+            day,
+            all,
+            year,
+            examples,
+            force,
+            wait,
+        } => {
+            let year = year.unwrap_or_else(current_event);
+            let client = Client::new(&cli.base_url, &profile)?;
+            if wait {
+                wait_for_input(&client, &profile, force)?;
+                return Ok(());
+            }
             if all {
-                return download_all_inputs();
+                return get_all(&client, &profile, year, examples, force);
             }
             if let Some(day) = day {
-                return download_input(day);
+                return get_day(&client, &profile, year, day, examples, force);
             }
             println!("No day parameter specified, attempting to download today's input");
-            let now_day = get_today()?;
+            let now_day = event::ensure_today(Utc::now())?;
             println!("Getting input for day {now_day}");
-            download_input(now_day)
+            get_day(&client, &profile, year, now_day, examples, force)
+        }
+        Commands::Submit { day, part, year } => {
+            let year = year.unwrap_or_else(current_event);
+            submit_answer(&cli.base_url, &profile, year, day, part.try_into()?)
+        }
+        Commands::Read { day, year, refresh } => {
+            let year = year.unwrap_or_else(current_event);
+            let text = read_puzzle(
+                &cli.base_url,
+                &profile,
+                year,
+                day,
+                refresh,
+                Style::for_stdout(),
+            )?;
+            println!("{text}");
+            Ok(())
+        }
+        Commands::Status { year, stars, tests } => {
+            let year = year.unwrap_or_else(latest_year);
+            print_status(&cli.base_url, &profile, year, stars, tests)
+        }
+        Commands::Leaderboard { id, year, day } => {
+            let year = year.unwrap_or_else(current_event);
+            print_leaderboard(&cli.base_url, &profile, year, id, day)
+        }
+        Commands::Whoami { endpoint } => {
+            let client = Client::new(&cli.base_url, &profile)?;
+            let user = client.whoami(&endpoint)?;
+            match profile.name() {
+                Some(name) => println!("Profile {name} is logged in as {user}"),
+                None => println!("Logged in as {user}"),
+            }
+            Ok(())
+        }
+        Commands::Verify { day, all, year } => {
+            let year = year.unwrap_or_else(latest_year);
+            if all {
+                return verify_days(&profile, year, solutions(year));
+            }
+            if let Some(day) = day {
+                return verify_days(&profile, year, [get_solution(year, day)?]);
+            }
+            println!("No day parameter specified, attempting to verify today's answers");
+            let now_day = event::ensure_today(Utc::now())?;
+            println!("Verifying day {now_day}");
+            verify_days(&profile, year, [get_solution(year, now_day)?])
+        }
+        Commands::Bench {
+            day,
+            all,
+            year,
+            warmup,
+            iterations,
+            baseline,
+            save,
+            threshold,
+            size,
+            seed,
+        } => {
+            let year = year.unwrap_or_else(latest_year);
+            let options = BenchOptions { warmup, iterations };
+            let threshold = threshold / 100.0;
+            if all {
+                return bench_days(
+                    &profile,
+                    year,
+                    solutions(year),
+                    &options,
+                    &baseline,
+                    save,
+                    threshold,
+                );
+            }
+            let day = match day {
+                Some(day) => day,
+                None => {
+                    println!("No day parameter specified, attempting to benchmark today's code");
+                    event::ensure_today(Utc::now())?
+                }
+            };
+            let solution = get_solution(year, day)?;
+            if let Some(size) = size {
+                return bench_generated(solution, seed, size, &options);
+            }
+            bench_days(
+                &profile,
+                year,
+                [solution],
+                &options,
+                &baseline,
+                save,
+                threshold,
+            )
+        }
+        Commands::Gen {
+            day,
+            year,
+            seed,
+            size,
+        } => {
+            let year = year.unwrap_or_else(latest_year);
+            print!("{}", get_solution(year, day)?.generate(seed, size)?);
+            Ok(())
+        }
+        Commands::Check {
+            day,
+            year,
+            cases,
+            max_size,
+        } => {
+            let year = year.unwrap_or_else(latest_year);
+            let examples = read_examples(year, day)?;
+            let compared = get_solution(year, day)?.check(cases, max_size, &examples)?;
+            println!("The fast and reference implementations agree on {compared} inputs");
+            Ok(())
+        }
+        Commands::New { day, year } => {
+            let year = year.unwrap_or_else(current_event);
+            for path in new_day(year, day, &example_path(year, day, 1))? {
+                println!("Created {path}");
+            }
+            Ok(())
+        }
+        Commands::List { year } => {
+            for solution in all_solutions().filter(|s| year.is_none_or(|year| s.year == year)) {
+                println!("{solution}");
+            }
+            Ok(())
         }
     }
 }
 
-fn get_today() -> Result<u32> {
-    let now = Local::now();
-    let now_day = now.day();
-    if now.month() == 12 && VALID_DAY_RANGE.contains(&now_day) {
-        Ok(now_day)
-    } else {
-        bail!("Today is not a valid Advent of Code day. Please specify a day");
-    }
-}
-
-#[allow(const_item_mutation)]
-fn run_all_days() -> Result<()> {
-    VALID_DAY_RANGE.try_for_each(run_day)
-}
-
-fn run_day(day: u32) -> Result<()> {
-    println!("======== DAY {day} ========");
-    // I'd like to do this with a macro, but I'm not sure how to do it.
-    let input_file = &format!("inputs/day{day:02}.txt");
-    match day {
-        1 => day01::Day01::run_day(input_file),
-        2 => day02::Day02::run_day(input_file),
-        3 => day03::Day03::run_day(input_file),
-        4 => day04::Day04::run_day(input_file),
-        5 => day05::Day05::run_day(input_file),
-        6 => day06::Day06::run_day(input_file),
-        7 => day07::Day07::run_day(input_file),
-        8 => day08::Day08::run_day(input_file),
-        9 => day09::Day09::run_day(input_file),
-        10 => day10::Day10::run_day(input_file),
-        11 => day11::Day11::run_day(input_file),
-        12 => day12::Day12::run_day(input_file),
-        13 => day13::Day13::run_day(input_file),
-        14 => day14::Day14::run_day(input_file),
-        15 => day15::Day15::run_day(input_file),
-        16 => day16::Day16::run_day(input_file),
-        17 => day17::Day17::run_day(input_file),
-        18 => day18::Day18::run_day(input_file),
-        19 => day19::Day19::run_day(input_file),
-        20 => day20::Day20::run_day(input_file),
-        21 => day21::Day21::run_day(input_file),
-        22 => day22::Day22::run_day(input_file),
-        23 => day23::Day23::run_day(input_file),
-        24 => day24::Day24::run_day(input_file),
-        25 => day25::Day25::run_day(input_file),
-        d => bail!("provided unsupported day {d}"),
-    }
-}
-
-#[allow(const_item_mutation)]
-fn download_all_inputs() -> Result<()> {
-    VALID_DAY_RANGE.try_for_each(download_input)
+/// The latest year with registered solutions, for the commands which run them (or the current event if there is none)
+fn latest_year() -> u32 {
+    aoc_2024::latest_year().unwrap_or_else(current_event)
 }
 
-fn download_input(day: u32) -> Result<()> {
-    // Read session cookie from .session file
-    let session = fs::read_to_string(".session").context("reading .session file")?;
-    let session = session.trim();
-    let url = format!("https://adventofcode.com/{YEAR}/day/{day}/input");
-
-    let client = reqwest::blocking::Client::new();
-    let response = client
-        .get(url)
-        .header("cookie", format!("session={session};"))
-        .send().context("sending HTTP request to download input")?
-        .error_for_status()
-        .with_context(|| format!("retrieving the input for day {day}. Do you have the correct session cookie in the .session file?"))?;
-
-    let text = response.text().context("decoding response body as text")?;
-    let path = format!("inputs/day{day:02}.txt");
-    fs::write(&path, text.trim_end_matches('\n')).context("writing input to file")?;
-    println!("Successfully downloaded input to {}", &path);
-    Ok(())
+/// The event of the current year, or of the previous one before December (in US-Eastern time), for the commands which
+/// fetch the website
+fn current_event() -> u32 {
+    event::current_event(Utc::now())
 }
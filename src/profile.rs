@@ -0,0 +1,247 @@
+//! Sessions and inputs of several Advent of Code accounts
+//!
+//! The session cookie of the default account comes from the `AOC_SESSION` environment variable, the `session` key of
+//! the config file or the `.session` file, in that order. Other accounts are named profiles in the config file, whose
+//! inputs, answers and submissions are stored separately. The contact sent to the website in the User-Agent comes from
+//! the `AOC_CONTACT` environment variable or the `contact` key of the config file, and is shared by all profiles:
+//!
+//! ```toml
+//! session = "53616c..."
+//! contact = "you@example.com"
+//!
+//! [profiles.alice]
+//! session = "8e2f1b..."
+//! ```
+use std::{collections::BTreeMap, env, fs, path::PathBuf};
+
+use anyhow::{bail, Context as _, Result};
+use serde::Deserialize;
+
+use crate::{
+    answers::ANSWERS_FILE, days::input_path, status::LAST_RUNS_FILE, submit::SUBMISSIONS_LOG,
+};
+
+/// The environment variable holding the session cookie of the default profile
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// The environment variable holding the contact sent in the User-Agent
+pub const CONTACT_VAR: &str = "AOC_CONTACT";
+
+/// The environment variable overriding the location of the config file
+pub const CONFIG_VAR: &str = "AOC_CONFIG";
+
+/// The file holding the session cookie of the default profile, relative to the working directory
+pub const SESSION_FILE: &str = ".session";
+
+/// The settings of a named profile
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProfileConfig {
+    pub session: String,
+}
+
+/// The user config file
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// The session cookie of the default profile
+    pub session: Option<String>,
+    /// How the website's maintainers can reach the user (e.g. an email address or a repository URL)
+    pub contact: Option<String>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, ProfileConfig>,
+}
+
+impl Config {
+    /// The location of the config file: `$AOC_CONFIG`, or `aoc/config.toml` in the user's config directory
+    pub fn path() -> Option<PathBuf> {
+        if let Some(path) = env::var_os(CONFIG_VAR) {
+            return Some(path.into());
+        }
+        let config_dir = env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+        Some(config_dir.join("aoc").join("config.toml"))
+    }
+
+    /// The contact sent in the User-Agent: `$AOC_CONTACT`, or the `contact` key of the config file
+    pub fn contact(&self) -> Option<String> {
+        env::var(CONTACT_VAR)
+            .ok()
+            .or_else(|| self.contact.clone())
+            .map(|contact| contact.trim().to_string())
+            .filter(|contact| !contact.is_empty())
+    }
+
+    /// Load the config file, which is empty if it doesn't exist
+    pub fn load() -> Result<Self> {
+        let Some(path) = Self::path() else {
+            return Ok(Self::default());
+        };
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("reading the config file {}", path.display()))
+            }
+        };
+        toml::from_str(&contents)
+            .with_context(|| format!("parsing the config file {}", path.display()))
+    }
+}
+
+/// An Advent of Code account, either the default one or a named profile of the config file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Profile {
+    name: Option<String>,
+}
+
+impl Profile {
+    /// The profile called `name`, or the default one
+    ///
+    /// Names are restricted to letters, digits, `-` and `_`, since they are used in paths.
+    pub fn new(name: Option<&str>) -> Result<Self> {
+        if let Some(name) = name {
+            if name.is_empty()
+                || !name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                bail!("invalid profile name {name:?}, use letters, digits, - and _");
+            }
+        }
+        Ok(Self {
+            name: name.map(str::to_string),
+        })
+    }
+
+    /// The name of the profile, `None` for the default one
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The directory where the files of a named profile are stored
+    fn dir(&self) -> Option<String> {
+        self.name
+            .as_ref()
+            .map(|name| format!("inputs/profiles/{name}"))
+    }
+
+    /// The location of the input file for `day` of `year`
+    ///
+    /// The inputs of the default profile are stored directly in `inputs/`, and those of a named profile in
+    /// `inputs/profiles/<name>/`.
+    pub fn input_path(&self, year: u32, day: u32) -> String {
+        match self.dir() {
+            Some(dir) => format!("{dir}/{year}/day{day:02}.txt"),
+            None => input_path(year, day),
+        }
+    }
+
+    /// The location of the accepted answers file, since they differ between accounts
+    pub fn answers_file(&self) -> String {
+        match self.dir() {
+            Some(dir) => format!("{dir}/{ANSWERS_FILE}"),
+            None => ANSWERS_FILE.to_string(),
+        }
+    }
+
+    /// The location of the submissions log
+    pub fn submissions_log(&self) -> String {
+        match self.dir() {
+            Some(dir) => format!("{dir}/submissions.log"),
+            None => SUBMISSIONS_LOG.to_string(),
+        }
+    }
+
+    /// The location of the last runs file, since the outcomes depend on the inputs
+    pub fn last_runs_file(&self) -> String {
+        match self.dir() {
+            Some(dir) => format!("{dir}/last_runs.toml"),
+            None => LAST_RUNS_FILE.to_string(),
+        }
+    }
+
+    /// The session cookie of the profile
+    ///
+    /// See the [module documentation](self) for where it is looked up.
+    pub fn session(&self, config: &Config) -> Result<String> {
+        let session = match &self.name {
+            Some(name) => match config.profiles.get(name) {
+                Some(profile) => profile.session.clone(),
+                None => bail!(
+                    "no profile {name} in the config file (available: {})",
+                    if config.profiles.is_empty() {
+                        "none".to_string()
+                    } else {
+                        config.profiles.keys().cloned().collect::<Vec<_>>().join(", ")
+                    }
+                ),
+            },
+            None => match (env::var(SESSION_VAR), &config.session) {
+                (Ok(session), _) => session,
+                (Err(_), Some(session)) => session.clone(),
+                (Err(_), None) => fs::read_to_string(SESSION_FILE).with_context(|| {
+                    format!("reading the {SESSION_FILE} file (the session cookie can also be set with {SESSION_VAR} or in the config file)")
+                })?,
+            },
+        };
+        let session = session.trim();
+        if session.is_empty() {
+            bail!("the session cookie is empty");
+        }
+        Ok(session.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"session = "default"
+
+[profiles.alice]
+session = " abc\n"
+"#;
+
+    #[test]
+    fn test_session() {
+        let config: Config = toml::from_str(CONFIG).unwrap();
+        let alice = Profile::new(Some("alice")).unwrap();
+        assert_eq!(alice.session(&config).unwrap(), "abc");
+        assert_eq!(
+            Profile::new(Some("bob"))
+                .unwrap()
+                .session(&config)
+                .unwrap_err()
+                .to_string(),
+            "no profile bob in the config file (available: alice)"
+        );
+        assert!(toml::from_str::<Config>("[profiles.alice]\ncookie = \"abc\"").is_err());
+    }
+
+    #[test]
+    fn test_paths() {
+        let default = Profile::new(None).unwrap();
+        assert_eq!(default.input_path(2024, 1), "inputs/2024/day01.txt");
+        assert_eq!(default.answers_file(), "answers.toml");
+        let alice = Profile::new(Some("alice")).unwrap();
+        assert_eq!(alice.name(), Some("alice"));
+        assert_eq!(
+            alice.input_path(2024, 1),
+            "inputs/profiles/alice/2024/day01.txt"
+        );
+        assert_eq!(alice.answers_file(), "inputs/profiles/alice/answers.toml");
+        assert_eq!(
+            alice.submissions_log(),
+            "inputs/profiles/alice/submissions.log"
+        );
+        assert_eq!(
+            alice.last_runs_file(),
+            "inputs/profiles/alice/last_runs.toml"
+        );
+        assert!(Profile::new(Some("../etc")).is_err());
+        assert!(Profile::new(Some("")).is_err());
+    }
+}
@@ -0,0 +1,339 @@
+use std::{fmt::Display, time::Duration};
+
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::{ser::SerializeMap as _, Serialize, Serializer};
+
+use crate::{alloc::AllocStats, answer::Answer, days::Part};
+
+/// The output format of the runner
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text
+    #[default]
+    Text,
+    /// One JSON object per line
+    Json,
+    /// Comma-separated values with a header row
+    Csv,
+}
+
+/// The answer to a part of the puzzle (or the reason why there is none) and how long it took to compute it
+#[derive(Debug, Clone, Serialize)]
+pub struct PartReport {
+    #[serde(flatten, serialize_with = "answer_or_error")]
+    pub answer: Result<Answer, String>,
+    #[serde(rename = "time_ns", serialize_with = "as_nanos")]
+    pub time: Duration,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alloc: Option<AllocStats>,
+}
+
+impl PartReport {
+    /// Create the report for a part from its outcome
+    pub fn new(answer: Result<Answer>, time: Duration) -> Self {
+        Self {
+            answer: answer.map_err(|e| format!("{e:#}")),
+            time,
+            alloc: None,
+        }
+    }
+}
+
+/// The outcome of running a day: answers, timings and error if any
+#[derive(Debug, Clone, Serialize)]
+pub struct RunReport {
+    pub year: u32,
+    pub day: u32,
+    #[serde(rename = "parse_ns", serialize_with = "opt_as_nanos")]
+    pub parse_time: Option<Duration>,
+    #[serde(rename = "prepare_ns", serialize_with = "opt_as_nanos")]
+    pub prepare_time: Option<Duration>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parse_alloc: Option<AllocStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prepare_alloc: Option<AllocStats>,
+    pub part_1: Option<PartReport>,
+    pub part_2: Option<PartReport>,
+    pub error: Option<String>,
+}
+
+impl RunReport {
+    /// Create an empty report for `day` of `year`
+    pub fn new(year: u32, day: u32) -> Self {
+        Self {
+            year,
+            day,
+            parse_time: None,
+            prepare_time: None,
+            parse_alloc: None,
+            prepare_alloc: None,
+            part_1: None,
+            part_2: None,
+            error: None,
+        }
+    }
+
+    /// The report for a part of the puzzle, if it was run
+    pub fn part(&self, part: Part) -> Option<&PartReport> {
+        match part {
+            Part::One => self.part_1.as_ref(),
+            Part::Two => self.part_2.as_ref(),
+        }
+    }
+
+    /// Record the report for a part of the puzzle
+    pub fn set_part(&mut self, part: Part, report: PartReport) {
+        match part {
+            Part::One => self.part_1 = Some(report),
+            Part::Two => self.part_2 = Some(report),
+        }
+    }
+
+    /// Whether the day or one of its parts failed
+    pub fn is_failure(&self) -> bool {
+        self.error.is_some()
+            || [&self.part_1, &self.part_2]
+                .into_iter()
+                .flatten()
+                .any(|p| p.answer.is_err())
+    }
+
+    /// The error of the day, or the errors of its parts if the day itself didn't fail
+    pub fn error_message(&self) -> Option<String> {
+        if let Some(error) = &self.error {
+            return Some(error.clone());
+        }
+        let errors = [Part::One, Part::Two]
+            .into_iter()
+            .filter_map(|part| {
+                let error = self.part(part)?.answer.as_ref().err()?;
+                Some(format!("part {}: {error}", part.number()))
+            })
+            .collect::<Vec<_>>();
+        (!errors.is_empty()).then(|| errors.join("; "))
+    }
+
+    /// The total time spent parsing, preparing and running the parts
+    pub fn total_time(&self) -> Duration {
+        self.parse_time.unwrap_or_default()
+            + self.prepare_time.unwrap_or_default()
+            + self.part_1.as_ref().map(|p| p.time).unwrap_or_default()
+            + self.part_2.as_ref().map(|p| p.time).unwrap_or_default()
+    }
+
+    /// The header row of the CSV output
+    pub fn csv_header() -> &'static str {
+        "year,day,\
+         parse_ns,parse_allocs,parse_bytes,parse_peak_bytes,\
+         prepare_ns,prepare_allocs,prepare_bytes,prepare_peak_bytes,\
+         part_1,part_1_ns,part_1_allocs,part_1_bytes,part_1_peak_bytes,\
+         part_2,part_2_ns,part_2_allocs,part_2_bytes,part_2_peak_bytes,\
+         error"
+    }
+
+    /// The report as a CSV row
+    pub fn to_csv(&self) -> String {
+        let nanos = |d: Option<Duration>| d.map(|d| d.as_nanos().to_string()).unwrap_or_default();
+        let answer = |p: Option<&PartReport>| {
+            p.and_then(|p| p.answer.as_ref().ok())
+                .map(|answer| csv_field(&answer.to_string()))
+                .unwrap_or_default()
+        };
+        format!(
+            "{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            self.year,
+            self.day,
+            nanos(self.parse_time),
+            csv_alloc(self.parse_alloc),
+            nanos(self.prepare_time),
+            csv_alloc(self.prepare_alloc),
+            answer(self.part_1.as_ref()),
+            nanos(self.part_1.as_ref().map(|p| p.time)),
+            csv_alloc(self.part_1.as_ref().and_then(|p| p.alloc)),
+            answer(self.part_2.as_ref()),
+            nanos(self.part_2.as_ref().map(|p| p.time)),
+            csv_alloc(self.part_2.as_ref().and_then(|p| p.alloc)),
+            self.error_message()
+                .as_deref()
+                .map(csv_field)
+                .unwrap_or_default()
+        )
+    }
+
+    /// The report as a single line of JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+}
+
+/// Human-readable output, in the same style as the original runner
+impl Display for RunReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "======== DAY {} ({}) ========", self.day, self.year)?;
+        if let Some(parse_time) = self.parse_time {
+            writeln!(f, "Parsing took {parse_time:?}{}", alloc(self.parse_alloc))?;
+        }
+        if let Some(prepare_time) = self.prepare_time {
+            writeln!(
+                f,
+                "Preparation took {prepare_time:?}{}",
+                alloc(self.prepare_alloc)
+            )?;
+        }
+        for part in [Part::One, Part::Two] {
+            if let Some(report) = self.part(part) {
+                match &report.answer {
+                    Ok(answer) => writeln!(f, "Part {}: {answer}", part.number())?,
+                    Err(error) => writeln!(f, "Part {} failed: {error}", part.number())?,
+                }
+                writeln!(
+                    f,
+                    "Part {} took {:?}{}",
+                    part.number(),
+                    report.time,
+                    alloc(report.alloc)
+                )?;
+            }
+        }
+        if let Some(error) = &self.error {
+            writeln!(f, "Error: {error}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The allocation statistics of a phase in parentheses, if they were measured
+fn alloc(stats: Option<AllocStats>) -> String {
+    stats.map(|s| format!(" ({s})")).unwrap_or_default()
+}
+
+/// The allocation count, allocated bytes and peak bytes of a phase as three CSV fields, empty if
+/// they were not measured
+fn csv_alloc(stats: Option<AllocStats>) -> String {
+    stats
+        .map(|s| format!("{},{},{}", s.allocations, s.bytes, s.peak_bytes))
+        .unwrap_or_else(|| ",,".to_string())
+}
+
+/// Quote a CSV field if it contains a separator, quote or newline
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn answer_or_error<S: Serializer>(
+    answer: &Result<Answer, String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut map = serializer.serialize_map(Some(2))?;
+    map.serialize_entry("answer", &answer.as_ref().ok())?;
+    map.serialize_entry("error", &answer.as_ref().err())?;
+    map.end()
+}
+
+fn as_nanos<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(duration.as_nanos() as u64)
+}
+
+fn opt_as_nanos<S: Serializer>(
+    duration: &Option<Duration>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match duration {
+        Some(duration) => as_nanos(duration, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+
+    use super::*;
+
+    fn report() -> RunReport {
+        let mut report = RunReport::new(2024, 17);
+        report.parse_time = Some(Duration::from_micros(12));
+        report.set_part(
+            Part::One,
+            PartReport::new(Ok("4,6,3".into()), Duration::from_nanos(2300)),
+        );
+        report
+    }
+
+    fn failed_report() -> RunReport {
+        let mut report = report();
+        report.set_part(
+            Part::Two,
+            PartReport::new(Err(anyhow!("no solution")), Duration::from_nanos(100)),
+        );
+        report
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(
+            report().to_csv(),
+            "2024,17,12000,,,,,,,,\"4,6,3\",2300,,,,,,,,,"
+        );
+        assert_eq!(
+            failed_report().to_csv(),
+            "2024,17,12000,,,,,,,,\"4,6,3\",2300,,,,,100,,,,part 2: no solution"
+        );
+        let mut report = report();
+        report.prepare_time = Some(Duration::from_micros(3));
+        assert_eq!(
+            report.to_csv(),
+            "2024,17,12000,,,,3000,,,,\"4,6,3\",2300,,,,,,,,,"
+        );
+        assert_eq!(
+            RunReport::csv_header().split(',').count(),
+            report.to_csv().split(',').count() - 2
+        );
+    }
+
+    #[test]
+    fn test_failure() {
+        assert!(!report().is_failure());
+        assert!(failed_report().is_failure());
+        let mut report = report();
+        report.error = Some("reading the input file".to_string());
+        assert!(report.is_failure());
+        assert_eq!(
+            report.error_message().as_deref(),
+            Some("reading the input file")
+        );
+    }
+
+    #[test]
+    fn test_alloc() {
+        let stats = AllocStats {
+            allocations: 2,
+            bytes: 2048,
+            peak_bytes: 1024,
+        };
+        let mut report = report();
+        report.parse_alloc = Some(stats);
+        assert!(report.to_csv().starts_with("2024,17,12000,2,2048,1024,,"));
+        assert_eq!(
+            report.to_string(),
+            "======== DAY 17 (2024) ========\nParsing took 12µs (2 allocations, 2.0 KiB allocated, 1.0 KiB peak)\nPart 1: 4,6,3\nPart 1 took 2.3µs\n"
+        );
+        assert!(report
+            .to_json()
+            .unwrap()
+            .contains(r#""parse_alloc":{"allocations":2,"bytes":2048,"peak_bytes":1024}"#));
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(
+            failed_report().to_json().unwrap(),
+            r#"{"year":2024,"day":17,"parse_ns":12000,"prepare_ns":null,"part_1":{"answer":"4,6,3","error":null,"time_ns":2300},"part_2":{"answer":null,"error":"no solution","time_ns":100},"error":null}"#
+        );
+    }
+}
@@ -0,0 +1,156 @@
+//! Running and verifying several days at once, as done by the `run` and `verify` subcommands
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Context as _, Result};
+use chrono::Utc;
+use itertools::Itertools as _;
+use rayon::{
+    iter::{IntoParallelRefIterator as _, ParallelIterator as _},
+    ThreadPoolBuilder,
+};
+
+use crate::{
+    answers::{Check, KnownAnswers},
+    days::{Param, Part, Solution},
+    profile::Profile,
+    report::{OutputFormat, RunReport},
+    status::{LastRun, LastRuns},
+};
+
+/// Run each solution with its input file and print the reports in `format`, followed by a summary for several days
+///
+/// The days run concurrently on `jobs` threads (all the cores by default), and a failing day doesn't prevent the others
+/// from running. The outcomes are recorded in the last runs file at `last_runs`, if any. Fails if any day failed.
+pub fn run_days<'a>(
+    days: impl IntoIterator<Item = (&'a Solution, String)>,
+    params: &[Param],
+    format: OutputFormat,
+    jobs: Option<usize>,
+    last_runs: Option<&str>,
+) -> Result<()> {
+    let days = days.into_iter().collect_vec();
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(jobs.unwrap_or_default())
+        .build()
+        .context("creating the thread pool")?;
+    let before_run = Instant::now();
+    // each day's outcome is collected independently, in order
+    let reports: Vec<_> = pool.install(|| {
+        days.par_iter()
+            .map(|(solution, path)| solution.run(path, params))
+            .collect()
+    });
+    let elapsed = before_run.elapsed();
+    if format == OutputFormat::Csv {
+        println!("{}", RunReport::csv_header());
+    }
+    for report in &reports {
+        match format {
+            OutputFormat::Text => print!("{report}"),
+            OutputFormat::Json => println!("{}", report.to_json()?),
+            OutputFormat::Csv => println!("{}", report.to_csv()),
+        }
+    }
+    if let Some(path) = last_runs {
+        let mut runs = LastRuns::load(path)?;
+        for report in &reports {
+            runs.set(
+                report.year,
+                report.day,
+                LastRun::new(report, None, Utc::now()),
+            );
+        }
+        runs.save(path)?;
+    }
+    let failures = reports.iter().filter(|r| r.is_failure()).count();
+    if format == OutputFormat::Text && reports.len() > 1 {
+        print_summary(&reports, elapsed, failures);
+    }
+    if failures > 0 {
+        bail!("{failures} day(s) failed");
+    }
+    Ok(())
+}
+
+fn print_summary(reports: &[RunReport], elapsed: Duration, failures: usize) {
+    println!("======== SUMMARY ========");
+    println!("Day | {:<20} | {:<20} | Time", "Part 1", "Part 2");
+    for report in reports {
+        let [part1, part2] = [Part::One, Part::Two].map(|part| {
+            report
+                .part(part)
+                .map_or("-".to_string(), |p| match &p.answer {
+                    Ok(answer) => answer.to_string(),
+                    Err(_) => "ERROR".to_string(),
+                })
+        });
+        match &report.error {
+            Some(error) => println!("{:>3} | ERROR: {error}", report.day),
+            None => println!(
+                "{:>3} | {part1:<20} | {part2:<20} | {:.1?}",
+                report.day,
+                report.total_time()
+            ),
+        }
+    }
+    let total: Duration = reports.iter().map(RunReport::total_time).sum();
+    println!(
+        "Ran {} day(s) in {elapsed:.1?} (sum of day times: {total:.1?}), {failures} failure(s)",
+        reports.len()
+    );
+}
+
+/// Run each solution with the input of `profile` and compare its answers with the accepted ones, printing a table
+///
+/// The outcomes are recorded in the last runs file of the profile. Fails if any answer doesn't match.
+pub fn verify_days<'a>(
+    profile: &Profile,
+    year: u32,
+    solutions: impl IntoIterator<Item = &'a Solution>,
+) -> Result<()> {
+    let answers = KnownAnswers::load(profile.answers_file())?;
+    let mut runs = LastRuns::load(profile.last_runs_file())?;
+    let (mut passed, mut failed, mut unrecorded) = (0, 0, 0);
+    println!("Day | {:<40} | Part 2", "Part 1");
+    for solution in solutions {
+        let expected = answers.get(year, solution.day);
+        let report = solution.run(profile.input_path(year, solution.day), &[]);
+        let checks = [Part::One, Part::Two].map(|part| {
+            if !solution.parts.contains(part) {
+                return None;
+            }
+            let answer = match (report.part(part), &report.error) {
+                (Some(part), _) => part.answer.clone().map_err(|e| anyhow!(e)),
+                (None, Some(error)) => Err(anyhow!("{error}")),
+                (None, None) => Err(anyhow!("part was not run")),
+            };
+            Some(Check::new(
+                answer,
+                expected.and_then(|answers| answers.get(part)),
+            ))
+        });
+        for check in checks.iter().flatten() {
+            match check {
+                Check::Pass => passed += 1,
+                Check::Unrecorded(_) => unrecorded += 1,
+                _ => failed += 1,
+            }
+        }
+        let [part1, part2] = checks
+            .each_ref()
+            .map(|check| check.as_ref().map_or("-".to_string(), |c| c.to_string()));
+        println!("{:>3} | {part1:<40} | {part2}", solution.day);
+        let checks = checks.into_iter().flatten().collect_vec();
+        runs.set(
+            year,
+            solution.day,
+            LastRun::new(&report, Some(&checks), Utc::now()),
+        );
+    }
+    runs.save(profile.last_runs_file())?;
+    println!("{passed} passed, {failed} failed, {unrecorded} unrecorded");
+    if failed > 0 {
+        bail!("{failed} answer(s) did not match the accepted answers");
+    }
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use std::{fs, path::Path};
+
+use anyhow::{bail, Context as _, Result};
+
+/// The skeleton of a solution module
+const TEMPLATE: &str = include_str!("../templates/day.rs");
+
+/// Render the solution module for `day`, with its tests reading the example file at `example_path`
+///
+/// The example path is relative to the root of the project.
+fn render_day(day: u32, example_path: &str) -> String {
+    TEMPLATE
+        .replace("{{DD}}", &format!("{day:02}"))
+        .replace("{{DAY}}", &day.to_string())
+        .replace("{{EXAMPLE}}", example_path)
+}
+
+/// Generate the solution module for `day` of `year`, returning the paths of the created files
+///
+/// The build script registers the new module (and its year, if needed) on the next build. An empty example file is
+/// created at `example_path` if none exists yet, for the generated tests to read.
+pub fn new_day(year: u32, day: u32, example_path: &str) -> Result<Vec<String>> {
+    let year_dir = format!("src/days/y{year}");
+    let day_path = format!("{year_dir}/day{day:02}.rs");
+    if Path::new(&day_path).exists() {
+        bail!("{day_path} already exists, refusing to overwrite it");
+    }
+    let mut created = Vec::new();
+    fs::create_dir_all(&year_dir).context("creating the year directory")?;
+    fs::write(&day_path, render_day(day, example_path)).context("writing the day module")?;
+    created.push(day_path);
+    if !Path::new(example_path).exists() {
+        if let Some(dir) = Path::new(example_path).parent() {
+            fs::create_dir_all(dir).context("creating the examples directory")?;
+        }
+        fs::write(example_path, "").context("writing the example file")?;
+        created.push(example_path.to_string());
+    }
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_day() {
+        let module = render_day(7, "inputs/2024/examples/day07-1.txt");
+        assert!(module.contains("pub struct Day07;"));
+        assert!(module.contains("const DAY: u32 = 7;"));
+        assert!(module.contains("const EXAMPLE: &str = \"inputs/2024/examples/day07-1.txt\";"));
+        assert!(!module.contains("{{"));
+    }
+}
@@ -0,0 +1,473 @@
+//! The progress of an event at a glance: which days are implemented, downloaded, run and solved
+use std::{
+    collections::BTreeMap,
+    env,
+    fmt::Display,
+    fs,
+    path::Path,
+    process::{Command, Stdio},
+};
+
+use anyhow::{bail, Context as _, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
+use itertools::Itertools as _;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    answers::Check,
+    client::Client,
+    days::{get_solution, Part},
+    event,
+    html::calendar_stars,
+    profile::Profile,
+    report::RunReport,
+    Answer, Solution,
+};
+
+/// The location of the last runs file, relative to the working directory
+pub const LAST_RUNS_FILE: &str = "inputs/last_runs.toml";
+
+/// How the last run of a day on its input went
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    /// The parts ran without error, but their answers were not checked
+    Ok,
+    /// The answers match the accepted ones (see the `verify` subcommand)
+    Verified,
+    /// A part failed, or its answer doesn't match the accepted one
+    Failed,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Ok => write!(f, "ok"),
+            Outcome::Verified => write!(f, "verified"),
+            Outcome::Failed => write!(f, "FAILED"),
+        }
+    }
+}
+
+/// The last run of a day on its input
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LastRun {
+    pub outcome: Outcome,
+    /// When the day was run, in RFC 3339 format
+    pub at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part2: Option<String>,
+}
+
+impl LastRun {
+    /// Summarize a run which ended at `at`, with the checks of its answers if they were verified
+    pub fn new(report: &RunReport, checks: Option<&[Check]>, at: DateTime<Utc>) -> Self {
+        let outcome = match checks {
+            _ if report.is_failure() => Outcome::Failed,
+            None => Outcome::Ok,
+            Some(checks)
+                if checks
+                    .iter()
+                    .any(|c| matches!(c, Check::Mismatch { .. } | Check::Error(_))) =>
+            {
+                Outcome::Failed
+            }
+            Some(checks) if checks.iter().all(|c| matches!(c, Check::Pass)) => Outcome::Verified,
+            Some(_) => Outcome::Ok,
+        };
+        let [part1, part2] = [Part::One, Part::Two].map(|part| {
+            report
+                .part(part)
+                .and_then(|p| p.answer.as_ref().ok())
+                .map(Answer::to_string)
+        });
+        Self {
+            outcome,
+            at: at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            part1,
+            part2,
+        }
+    }
+}
+
+/// The last run of each day, keyed by year and then by day like the answers file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct LastRuns(BTreeMap<String, BTreeMap<String, LastRun>>);
+
+impl LastRuns {
+    /// Load the last runs file at `path`, which is empty if the file doesn't exist yet
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e).context("reading the last runs file"),
+        };
+        toml::from_str(&contents).context("parsing the last runs file")
+    }
+
+    /// Save the last runs to the file at `path`
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("creating the inputs directory")?;
+        }
+        let contents = toml::to_string(self).context("serializing the last runs")?;
+        fs::write(path, contents).context("writing the last runs file")
+    }
+
+    /// The last run of `day` of `year`, if any
+    pub fn get(&self, year: u32, day: u32) -> Option<&LastRun> {
+        self.0.get(&year.to_string())?.get(&day.to_string())
+    }
+
+    /// Record the last run of `day` of `year`
+    pub fn set(&mut self, year: u32, day: u32, run: LastRun) {
+        self.0
+            .entry(year.to_string())
+            .or_default()
+            .insert(day.to_string(), run);
+    }
+}
+
+/// How many unit tests of a day passed and failed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TestResults {
+    pub passed: usize,
+    pub failed: usize,
+}
+
+impl Display for TestResults {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.passed, self.failed) {
+            (0, 0) => write!(f, "none"),
+            (passed, 0) => write!(f, "ok ({passed})"),
+            (passed, failed) => write!(f, "FAIL {failed}/{}", passed + failed),
+        }
+    }
+}
+
+/// The results of the unit tests of each day of `year`, from the output of `cargo test`
+///
+/// The tests which were ignored are not counted.
+pub fn parse_test_results(output: &str, year: u32) -> BTreeMap<u32, TestResults> {
+    let prefix = format!("test days::y{year}::day");
+    let mut results = BTreeMap::<u32, TestResults>::new();
+    for line in output.lines() {
+        let Some((name, outcome)) = line
+            .strip_prefix(&prefix)
+            .and_then(|rest| rest.split_once(" ... "))
+        else {
+            continue;
+        };
+        let Some(day) = name.get(..2).and_then(|day| day.parse().ok()) else {
+            continue;
+        };
+        let day_results = results.entry(day).or_default();
+        match outcome {
+            "ok" => day_results.passed += 1,
+            "FAILED" => day_results.failed += 1,
+            _ => {}
+        }
+    }
+    results
+}
+
+/// Run the unit tests of the solutions of `year` with `cargo test`, and collect the results of each day
+///
+/// Cargo's own messages (e.g. the compilation progress) are shown as they come.
+pub fn run_tests(year: u32) -> Result<BTreeMap<u32, TestResults>> {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let output = Command::new(cargo)
+        .args(["test", "--lib", "--", &format!("days::y{year}::")])
+        .stderr(Stdio::inherit())
+        .output()
+        .context("running cargo test")?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // failing tests also make cargo exit with an error, but they still print their results
+    if !stdout.contains("test result:") {
+        bail!("cargo test didn't run the tests ({})", output.status);
+    }
+    Ok(parse_test_results(&stdout, year))
+}
+
+/// The progress on a day of an event
+#[derive(Clone)]
+pub struct DayStatus {
+    pub day: u32,
+    pub solution: Option<&'static Solution>,
+    pub has_input: bool,
+    pub last_run: Option<LastRun>,
+    /// The results of the unit tests, if they were run
+    pub tests: Option<TestResults>,
+    /// The stars of the account, if the calendar was fetched and the day is unlocked
+    pub stars: Option<u8>,
+}
+
+impl DayStatus {
+    /// The header of the status table
+    pub const HEADER: &'static str =
+        "Day | Title                    | Input | Last run                   | Tests    | Part 2 | Stars";
+
+    /// Whether part 2 is implemented, according to the parts declared by the solution
+    ///
+    /// The last day of an event has no part 2 (`n/a`), while on the other days a missing part 2 is still a `stub`,
+    /// like the one of the template.
+    pub fn part_2(&self) -> &'static str {
+        let Some(solution) = self.solution else {
+            return "-";
+        };
+        if solution.parts.contains(Part::Two) {
+            "yes"
+        } else if solution.day == *event::days(solution.year).end() {
+            "n/a"
+        } else {
+            "stub"
+        }
+    }
+}
+
+/// A row of the status table
+impl Display for DayStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let title = self.solution.map_or("-", |s| s.title);
+        let input = if self.has_input { "yes" } else { "no" };
+        let last_run =
+            self.last_run.as_ref().map_or(
+                "-".to_string(),
+                |run| match DateTime::parse_from_rfc3339(&run.at) {
+                    Ok(at) => format!("{} ({})", run.outcome, at.to_utc().format("%Y-%m-%d %H:%M")),
+                    Err(_) => run.outcome.to_string(),
+                },
+            );
+        let tests = self
+            .tests
+            .map_or("-".to_string(), |tests| tests.to_string());
+        let stars = self
+            .stars
+            .map_or(String::new(), |stars| "*".repeat(stars.into()));
+        write!(
+            f,
+            "{:>3} | {title:<24} | {input:<5} | {last_run:<26} | {tests:<8} | {:<6} | {stars}",
+            self.day,
+            self.part_2()
+        )
+    }
+}
+
+/// Print a row per day of the event with its progress, and the totals
+///
+/// With `stars`, the calendar of the account is downloaded, and with `tests`, the unit tests are run (see
+/// [`run_tests`]).
+pub fn print_status(
+    base_url: &str,
+    profile: &Profile,
+    year: u32,
+    stars: bool,
+    tests: bool,
+) -> Result<()> {
+    let calendar = if stars {
+        let client = Client::new(base_url, profile)?;
+        Some(calendar_stars(&client.calendar(year)?))
+    } else {
+        None
+    };
+    let test_results = if tests { Some(run_tests(year)?) } else { None };
+    let runs = LastRuns::load(profile.last_runs_file())?;
+    let statuses = event::days(year)
+        .map(|day| DayStatus {
+            day,
+            solution: get_solution(year, day).ok(),
+            has_input: fs::exists(profile.input_path(year, day)).unwrap_or_default(),
+            last_run: runs.get(year, day).cloned(),
+            tests: test_results
+                .as_ref()
+                .map(|results| results.get(&day).copied().unwrap_or_default()),
+            stars: calendar
+                .as_ref()
+                .and_then(|calendar| calendar.get(&day).copied()),
+        })
+        .collect_vec();
+    println!("{}", DayStatus::HEADER);
+    for status in &statuses {
+        println!("{status}");
+    }
+    let count = |f: fn(&DayStatus) -> bool| statuses.iter().filter(|s| f(s)).count();
+    print!(
+        "{} implemented, {} inputs, {} verified, {} failed",
+        count(|s| s.solution.is_some()),
+        count(|s| s.has_input),
+        count(|s| s
+            .last_run
+            .as_ref()
+            .is_some_and(|run| run.outcome == Outcome::Verified)),
+        count(|s| s
+            .last_run
+            .as_ref()
+            .is_some_and(|run| run.outcome == Outcome::Failed)),
+    );
+    if tests {
+        print!(
+            ", {} with failing tests",
+            count(|s| s.tests.is_some_and(|tests| tests.failed > 0))
+        );
+    }
+    match calendar {
+        Some(calendar) => println!(
+            ", {} stars",
+            calendar.values().map(|&s| u32::from(s)).sum::<u32>()
+        ),
+        None => println!(),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{days::fixture::Buggy, report::PartReport};
+
+    static BUGGY: Solution = Solution::new::<Buggy>(2024);
+
+    #[test]
+    fn test_parse_test_results() {
+        let results = parse_test_results(include_str!("../tests/fixtures/cargo_test.txt"), 2024);
+        assert_eq!(
+            results.into_iter().collect::<Vec<_>>(),
+            [
+                (
+                    5,
+                    TestResults {
+                        passed: 1,
+                        failed: 0
+                    }
+                ),
+                (
+                    7,
+                    TestResults {
+                        passed: 1,
+                        failed: 1
+                    }
+                ),
+                (
+                    9,
+                    TestResults {
+                        passed: 1,
+                        failed: 0
+                    }
+                ),
+                (
+                    18,
+                    TestResults {
+                        passed: 1,
+                        failed: 0
+                    }
+                ),
+            ]
+        );
+        assert!(
+            parse_test_results(include_str!("../tests/fixtures/cargo_test.txt"), 2023).is_empty()
+        );
+        assert_eq!(TestResults::default().to_string(), "none");
+        assert_eq!(
+            TestResults {
+                passed: 2,
+                failed: 0
+            }
+            .to_string(),
+            "ok (2)"
+        );
+        assert_eq!(
+            TestResults {
+                passed: 1,
+                failed: 1
+            }
+            .to_string(),
+            "FAIL 1/2"
+        );
+    }
+
+    #[test]
+    fn test_last_run() {
+        let at = DateTime::from_timestamp(1733029200, 0).unwrap();
+        let mut report = RunReport::new(2024, 1);
+        report.set_part(
+            Part::One,
+            PartReport::new(Ok(Answer::Integer(11)), Duration::ZERO),
+        );
+        report.set_part(
+            Part::Two,
+            PartReport::new(Ok(Answer::Integer(0)), Duration::ZERO),
+        );
+        let run = LastRun::new(&report, None, at);
+        assert_eq!(run.outcome, Outcome::Ok);
+        assert_eq!(run.part2.as_deref(), Some("0"));
+        let checks = [Check::Pass, Check::Unrecorded(Answer::Integer(0))];
+        assert_eq!(
+            LastRun::new(&report, Some(&checks), at).outcome,
+            Outcome::Ok
+        );
+        assert_eq!(
+            LastRun::new(&report, Some(&[Check::Pass]), at).outcome,
+            Outcome::Verified
+        );
+        let mut runs = LastRuns::default();
+        runs.set(2024, 1, run.clone());
+        let reloaded: LastRuns = toml::from_str(&toml::to_string(&runs).unwrap()).unwrap();
+        assert_eq!(reloaded.get(2024, 1), Some(&run));
+        assert!(reloaded.get(2024, 2).is_none());
+    }
+
+    #[test]
+    fn test_row() {
+        let at = DateTime::from_timestamp(1733029200, 0).unwrap();
+        let mut report = RunReport::new(2024, 1);
+        report.set_part(
+            Part::Two,
+            PartReport::new(Ok(Answer::Integer(0)), Duration::ZERO),
+        );
+        let status = DayStatus {
+            day: 1,
+            solution: Some(get_solution(2024, 1).unwrap()),
+            has_input: true,
+            last_run: Some(LastRun::new(&report, None, at)),
+            tests: Some(TestResults {
+                passed: 2,
+                failed: 0,
+            }),
+            stars: Some(1),
+        };
+        assert_eq!(
+            status.to_string(),
+            "  1 | Historian Hysteria       | yes   | ok (2024-12-01 05:00)      | ok (2)   | yes    | *"
+        );
+        let stub = DayStatus {
+            day: 3,
+            solution: Some(&BUGGY),
+            tests: None,
+            ..status
+        };
+        assert_eq!(stub.part_2(), "stub");
+        let day25 = DayStatus {
+            day: 25,
+            solution: Some(get_solution(2024, 25).unwrap()),
+            has_input: false,
+            last_run: None,
+            tests: None,
+            stars: None,
+        };
+        assert_eq!(day25.part_2(), "n/a");
+        let missing = DayStatus {
+            solution: None,
+            ..day25
+        };
+        assert_eq!(
+            missing.to_string(),
+            " 25 | -                        | no    | -                          | -        | -      | "
+        );
+    }
+}
@@ -0,0 +1,319 @@
+use std::{
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::Write as _,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+    time::Duration,
+};
+
+use anyhow::{bail, Context as _, Result};
+use regex::Regex;
+
+use crate::{
+    answer::Answer,
+    answers::KnownAnswers,
+    client::Client,
+    days::{get_solution, Part},
+    profile::Profile,
+};
+
+/// The location of the submissions log, relative to the working directory
+pub const SUBMISSIONS_LOG: &str = "inputs/submissions.log";
+
+/// Extract the remaining wait time from a rate-limiting message, e.g. "You have 1m 12s left to wait"
+static WAIT_TIME: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"You have (?:(\d+)m)?\s*(?:(\d+)s)? left to wait").unwrap());
+
+/// The server's verdict after submitting an answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// An answer was submitted too recently, we must wait for the indicated duration
+    RateLimited(Duration),
+    /// The part was already solved (or is not unlocked yet)
+    AlreadySolved,
+}
+
+impl Verdict {
+    /// Interpret the HTML page returned by the server after submitting an answer
+    pub fn from_html(html: &str) -> Result<Self> {
+        let verdict = if html.contains("That's the right answer") {
+            Verdict::Correct
+        } else if html.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if html.contains("your answer is too low") {
+            Verdict::TooLow
+        } else if html.contains("That's not the right answer") {
+            Verdict::Wrong
+        } else if html.contains("You gave an answer too recently") {
+            let wait = WAIT_TIME
+                .captures(html)
+                .map(|caps| {
+                    let minutes: u64 = caps.get(1).map_or(0, |m| m.as_str().parse().unwrap());
+                    let seconds: u64 = caps.get(2).map_or(0, |s| s.as_str().parse().unwrap());
+                    Duration::from_secs(minutes * 60 + seconds)
+                })
+                .unwrap_or_default();
+            Verdict::RateLimited(wait)
+        } else if html.contains("You don't seem to be solving the right level") {
+            Verdict::AlreadySolved
+        } else {
+            bail!("could not understand the server's response");
+        };
+        Ok(verdict)
+    }
+
+    /// The keyword used to represent the verdict in the submissions log
+    ///
+    /// Verdicts which say nothing about the answer itself are not logged.
+    fn log_key(self) -> Option<&'static str> {
+        match self {
+            Verdict::Correct => Some("correct"),
+            Verdict::TooHigh => Some("too-high"),
+            Verdict::TooLow => Some("too-low"),
+            Verdict::Wrong => Some("wrong"),
+            Verdict::RateLimited(_) | Verdict::AlreadySolved => None,
+        }
+    }
+
+    /// Parse a verdict from its keyword in the submissions log
+    fn from_log_key(key: &str) -> Option<Self> {
+        match key {
+            "correct" => Some(Verdict::Correct),
+            "too-high" => Some(Verdict::TooHigh),
+            "too-low" => Some(Verdict::TooLow),
+            "wrong" => Some(Verdict::Wrong),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "That's the right answer!"),
+            Verdict::TooHigh => write!(f, "That's not the right answer, it's too high"),
+            Verdict::TooLow => write!(f, "That's not the right answer, it's too low"),
+            Verdict::Wrong => write!(f, "That's not the right answer"),
+            Verdict::RateLimited(wait) => {
+                write!(f, "An answer was submitted too recently, wait {wait:?}")
+            }
+            Verdict::AlreadySolved => write!(f, "This part was already solved"),
+        }
+    }
+}
+
+/// An answer which was submitted, along with the verdict
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub year: u32,
+    pub day: u32,
+    pub part: Part,
+    pub answer: Answer,
+    pub verdict: Verdict,
+}
+
+impl Submission {
+    /// Whether this submission concerns the same puzzle part
+    fn is_for(&self, year: u32, day: u32, part: Part) -> bool {
+        self.year == year && self.day == day && self.part == part
+    }
+}
+
+/// The local log of submitted answers, one tab-separated [`Submission`] per line
+#[derive(Debug)]
+pub struct SubmissionLog {
+    path: PathBuf,
+    submissions: Vec<Submission>,
+}
+
+impl SubmissionLog {
+    /// Load the log at `path`, which is empty if the file doesn't exist yet
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e).context("reading the submissions log"),
+        };
+        let submissions = contents
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(|line| {
+                let mut fields = line.splitn(5, '\t');
+                let (Some(year), Some(day), Some(part), Some(verdict), Some(answer)) = (
+                    fields.next(),
+                    fields.next(),
+                    fields.next(),
+                    fields.next(),
+                    fields.next(),
+                ) else {
+                    bail!("invalid line in the submissions log: {line}");
+                };
+                Ok(Submission {
+                    year: year.parse()?,
+                    day: day.parse()?,
+                    part: part.parse::<u8>()?.try_into()?,
+                    answer: answer.into(),
+                    verdict: Verdict::from_log_key(verdict).with_context(|| {
+                        format!("invalid verdict in the submissions log: {verdict}")
+                    })?,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { path, submissions })
+    }
+
+    /// Check whether `answer` is worth submitting, knowing the previous submissions
+    ///
+    /// An answer is refused if the part was already solved, if the same answer was already submitted, or if a
+    /// previous submission tells us that the answer is too high or too low. Answers are compared once normalised, so
+    /// `042` is the same answer as `42`.
+    pub fn check(&self, year: u32, day: u32, part: Part, answer: &Answer) -> Result<()> {
+        for sub in self
+            .submissions
+            .iter()
+            .filter(|s| s.is_for(year, day, part))
+        {
+            if sub.verdict == Verdict::Correct {
+                bail!("this part was already solved with answer {}", sub.answer);
+            }
+            if sub.answer == *answer {
+                bail!("answer {answer} was already submitted: {}", sub.verdict);
+            }
+            let (Answer::Integer(value), Answer::Integer(previous)) = (answer, &sub.answer) else {
+                continue;
+            };
+            match sub.verdict {
+                Verdict::TooHigh if value >= previous => {
+                    bail!("answer {answer} is too high, {previous} was already too high");
+                }
+                Verdict::TooLow if value <= previous => {
+                    bail!("answer {answer} is too low, {previous} was already too low");
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Append a submission to the log, unless its verdict says nothing about the answer
+    pub fn record(&mut self, submission: Submission) -> Result<()> {
+        let Some(key) = submission.verdict.log_key() else {
+            return Ok(());
+        };
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).context("creating the submissions log directory")?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .context("opening the submissions log")?;
+        writeln!(
+            file,
+            "{}\t{}\t{}\t{key}\t{}",
+            submission.year,
+            submission.day,
+            submission.part.number(),
+            submission.answer
+        )
+        .context("writing to the submissions log")?;
+        self.submissions.push(submission);
+        Ok(())
+    }
+}
+
+/// Compute the answer to `part` of `day` of `year` with the input of `profile` and submit it, unless it was already
+/// submitted
+///
+/// A correct answer is recorded in the accepted answers of the profile, and every submission in its log.
+pub fn submit_answer(
+    base_url: &str,
+    profile: &Profile,
+    year: u32,
+    day: u32,
+    part: Part,
+) -> Result<()> {
+    let solution = get_solution(year, day)?;
+    let answer = solution.solve(profile.input_path(year, day), part)?;
+    println!("Day {day} part {} answer: {answer}", part.number());
+    let mut log = SubmissionLog::load(profile.submissions_log())?;
+    log.check(year, day, part, &answer)
+        .context("refusing to submit the answer")?;
+    let client = Client::new(base_url, profile)?;
+    let html = client.submit(year, day, part, &answer)?;
+    let verdict = Verdict::from_html(&html)?;
+    println!("{verdict}");
+    if verdict == Verdict::Correct {
+        let mut answers = KnownAnswers::load(profile.answers_file())?;
+        answers.set(year, day, part, &answer);
+        answers.save(profile.answers_file())?;
+    }
+    log.record(Submission {
+        year,
+        day,
+        part,
+        answer,
+        verdict,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CORRECT: &str = "<main><article><p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer to finding the Chief Historian.</p></article></main>";
+
+    const TOO_HIGH: &str = "<main><article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href=\"/2024/about\">about page</a>. Please wait one minute before trying again. <a href=\"/2024/day/1\">[Return to Day 1]</a></p></article></main>";
+
+    const WRONG: &str = "<main><article><p>That's not the right answer.  If you're stuck, make sure you're using the full input data. Please wait one minute before trying again.</p></article></main>";
+
+    const RATE_LIMITED: &str = "<main><article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 12s left to wait. <a href=\"/2024/day/1\">[Return to Day 1]</a></p></article></main>";
+
+    const ALREADY_SOLVED: &str = "<main><article><p>You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2024/day/1\">[Return to Day 1]</a></p></article></main>";
+
+    #[test]
+    fn test_verdict() {
+        assert_eq!(Verdict::from_html(CORRECT).unwrap(), Verdict::Correct);
+        assert_eq!(Verdict::from_html(TOO_HIGH).unwrap(), Verdict::TooHigh);
+        assert_eq!(Verdict::from_html(WRONG).unwrap(), Verdict::Wrong);
+        assert_eq!(
+            Verdict::from_html(RATE_LIMITED).unwrap(),
+            Verdict::RateLimited(Duration::from_secs(72))
+        );
+        assert_eq!(
+            Verdict::from_html(ALREADY_SOLVED).unwrap(),
+            Verdict::AlreadySolved
+        );
+        assert!(Verdict::from_html("<html></html>").is_err());
+    }
+
+    #[test]
+    fn test_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("submissions.log");
+        let mut log = SubmissionLog::load(&path).unwrap();
+        for (answer, verdict) in [("100", Verdict::TooHigh), ("10", Verdict::TooLow)] {
+            log.record(Submission {
+                year: 2024,
+                day: 1,
+                part: Part::One,
+                answer: answer.into(),
+                verdict,
+            })
+            .unwrap();
+        }
+        let log = SubmissionLog::load(&path).unwrap();
+        assert!(log.check(2024, 1, Part::One, &"100".into()).is_err());
+        assert!(log.check(2024, 1, Part::One, &"0100".into()).is_err());
+        assert!(log.check(2024, 1, Part::One, &150u64.into()).is_err());
+        assert!(log.check(2024, 1, Part::One, &"5".into()).is_err());
+        assert!(log.check(2024, 1, Part::One, &50u64.into()).is_ok());
+        assert!(log.check(2024, 1, Part::Two, &"100".into()).is_ok());
+    }
+}
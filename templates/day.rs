@@ -0,0 +1,61 @@
+use anyhow::Result;
+use enumflags2::{make_bitflags, BitFlags};
+use winnow::{combinator::rest, PResult, Parser as _};
+
+use crate::days::{Day, Part};
+
+pub struct Day{{DD}};
+
+impl Day for Day{{DD}} {
+    const DAY: u32 = {{DAY}};
+
+    const TITLE: &'static str = "TODO";
+
+    /// Add `Part::Two` once it is implemented
+    const PARTS: BitFlags<Part> = make_bitflags!(Part::One);
+
+    type Input = String;
+
+    type Params = ();
+
+    fn parser(input: &mut &str) -> PResult<Self::Input> {
+        rest.map(str::to_string).parse_next(input)
+    }
+
+    type Prepared = ();
+
+    type Output1 = usize;
+
+    fn part_1(_input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output1> {
+        Ok(0)
+    }
+
+    type Output2 = usize;
+
+    fn part_2(_input: &Self::Input, _prepared: &Self::Prepared) -> Result<Self::Output2> {
+        Ok(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::read_input;
+
+    /// The first example, read when the tests run since the example files are not committed
+    const EXAMPLE: &str = "{{EXAMPLE}}";
+
+    #[test]
+    fn test_part1() {
+        let input = read_input(EXAMPLE).unwrap();
+        let parsed = Day{{DD}}::parser(&mut input.as_str()).unwrap();
+        assert_eq!(Day{{DD}}::part_1(&parsed, &()).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = read_input(EXAMPLE).unwrap();
+        let parsed = Day{{DD}}::parser(&mut input.as_str()).unwrap();
+        assert_eq!(Day{{DD}}::part_2(&parsed, &()).unwrap(), 0);
+    }
+}